@@ -0,0 +1,534 @@
+//! # minimal-macros
+//! The `html!` macro of Minimal. Use it as `Minimal::html!` with the `macros` feature,
+//! it turns JSX-like markup into calls to the Minimal element builder.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{braced, token, Expr, Ident, Lit, LitStr, Pat, Token};
+
+/// Build a DOM tree from markup, called by `Minimal::html!` with the path of Minimal
+/// before a `;`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let markup = syn::parse_macro_input!(input as Markup);
+    markup.expand().into()
+}
+
+/// The whole macro input: the path of Minimal and one root element.
+struct Markup {
+    krate: TokenTree,
+    root: Element,
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            return Err(input.error("call `Minimal::html!`, which passes the path of Minimal to this macro"));
+        }
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        if !input.peek(Token![<]) {
+            return Err(input.error("html! expects a root element, like `<div>...</div>`"));
+        }
+        let root = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("html! expects a single root element, wrap these in a parent element"));
+        }
+        Ok(Markup { krate, root })
+    }
+}
+
+impl Markup {
+    fn expand(&self) -> TokenStream2 {
+        let root = self.root.expand(&self.krate, Namespace::Html);
+        quote! { #root.build() }
+    }
+}
+
+/// A tag or attribute name, which may contain `-` like `aria-label` or `my-widget`.
+struct Name {
+    text: String,
+    span: Span,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = Ident::parse_any(input)?;
+        let mut text = first.unraw().to_string();
+        let mut span = first.span();
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            let next = Ident::parse_any(input)?;
+            text.push('-');
+            text.push_str(&next.unraw().to_string());
+            span = span.join(next.span()).unwrap_or(span);
+        }
+        Ok(Name { text, span })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Namespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+/// `<name attrs>children</name>` or `<name attrs />`.
+struct Element {
+    name: Name,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Name = input.parse()?;
+        let mut attrs = Vec::new();
+        while !input.peek(Token![>]) && !input.peek(Token![/]) {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("unclosed tag `<{}`, expected `>` or `/>`", name.text),
+                ));
+            }
+            attrs.push(input.parse()?);
+        }
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Element {
+                name,
+                attrs,
+                children: Vec::new(),
+            });
+        }
+        input.parse::<Token![>]>()?;
+        let mut children = Vec::new();
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("unclosed tag `<{}>`, expected `</{}>`", name.text, name.text),
+                ));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                input.parse::<Token![<]>()?;
+                input.parse::<Token![/]>()?;
+                let close: Name = input.parse()?;
+                if close.text != name.text {
+                    return Err(syn::Error::new(
+                        close.span,
+                        format!("expected `</{}>`, found `</{}>`", name.text, close.text),
+                    ));
+                }
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            children.push(input.parse()?);
+        }
+        Ok(Element {
+            name,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl Element {
+    /// Expand to a block evaluating to the ElementBuilder, not built yet.
+    fn expand(&self, krate: &TokenTree, parent: Namespace) -> TokenStream2 {
+        let tag = &self.name.text;
+        let namespace = match tag.as_str() {
+            "svg" => Namespace::Svg,
+            "math" => Namespace::MathMl,
+            _ => parent,
+        };
+        let constructor = match namespace {
+            Namespace::Html => quote_spanned! {self.name.span=> #krate::el(#tag) },
+            Namespace::Svg => quote_spanned! {self.name.span=> #krate::svg(#tag) },
+            Namespace::MathMl => quote_spanned! {self.name.span=> #krate::mathml(#tag) },
+        };
+        let children_namespace = if tag == "foreignObject" {
+            Namespace::Html
+        } else {
+            namespace
+        };
+        let b = builder_ident();
+        let attrs = self.attrs.iter().map(|attr| attr.expand());
+        let children = expand_nodes(&self.children, krate, children_namespace);
+        quote! {
+            {
+                let #b = #constructor;
+                #(#attrs)*
+                #children
+                #b
+            }
+        }
+    }
+}
+
+/// The builder variable, hygienic so it never clashes with the user expressions.
+fn builder_ident() -> Ident {
+    Ident::new("__minimal_builder", Span::mixed_site())
+}
+
+/// The DOM events written as `onclick={...}`. Any other name starting with `on`, like
+/// `open`, is an attribute, and `on:name` listens to any event.
+const EVENTS: &[&str] = &[
+    "abort", "animationcancel", "animationend", "animationiteration", "animationstart", "auxclick",
+    "beforeinput", "beforetoggle", "blur", "cancel", "canplay", "canplaythrough", "change", "click", "close",
+    "compositionend", "compositionstart", "compositionupdate", "contextmenu", "copy", "cut", "dblclick", "drag",
+    "dragend", "dragenter", "dragleave", "dragover", "dragstart", "drop", "durationchange", "ended", "error",
+    "focus", "focusin", "focusout", "gotpointercapture", "input", "invalid", "keydown", "keypress", "keyup",
+    "load", "loadeddata", "loadedmetadata", "loadstart", "lostpointercapture", "mousedown", "mouseenter",
+    "mouseleave", "mousemove", "mouseout", "mouseover", "mouseup", "paste", "pause", "play", "playing",
+    "pointercancel", "pointerdown", "pointerenter", "pointerleave", "pointermove", "pointerout", "pointerover",
+    "pointerup", "progress", "ratechange", "reset", "resize", "scroll", "scrollend", "seeked", "seeking",
+    "select", "selectionchange", "slotchange", "stalled", "submit", "suspend", "timeupdate", "toggle",
+    "touchcancel", "touchend", "touchmove", "touchstart", "transitioncancel", "transitionend", "transitionrun",
+    "transitionstart", "volumechange", "waiting", "wheel",
+];
+
+enum AttrKind {
+    /// `name=value`, set with `attr()`.
+    Plain(String),
+    /// `class="a b"` or `class={expr}`, split on whitespace.
+    Classes,
+    /// `class:name={condition}`.
+    Class(String),
+    /// `style:property=value`.
+    Style(String),
+    /// `onclick={closure}` or `on:custom={closure}`.
+    Event(String),
+}
+
+enum Value {
+    Lit(Lit),
+    Expr(Expr),
+}
+
+struct Attr {
+    kind: AttrKind,
+    span: Span,
+    value: Option<Value>,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Name = input.parse()?;
+        let kind = if input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            let key: Name = input.parse()?;
+            match name.text.as_str() {
+                "class" => AttrKind::Class(key.text),
+                "style" => AttrKind::Style(key.text),
+                "on" => AttrKind::Event(key.text),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span,
+                        format!("unknown prefix `{}:`, expected `class:`, `style:` or `on:`", name.text),
+                    ))
+                }
+            }
+        } else if name.text == "class" {
+            AttrKind::Classes
+        } else if let Some(event) = name.text.strip_prefix("on").filter(|event| EVENTS.contains(event)) {
+            AttrKind::Event(event.to_owned())
+        } else {
+            AttrKind::Plain(name.text)
+        };
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                Some(Value::Expr(content.parse()?))
+            } else if input.peek(Lit) {
+                Some(Value::Lit(input.parse()?))
+            } else {
+                return Err(input.error("expected a literal or `{expression}` as attribute value"));
+            }
+        } else {
+            None
+        };
+        match (&kind, &value) {
+            (AttrKind::Event(_), Some(Value::Expr(_))) => {}
+            (AttrKind::Event(event), _) => {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("`{}` expects a closure in braces, like `={{|event| ...}}`", event),
+                ))
+            }
+            (AttrKind::Classes, None) | (AttrKind::Style(_), None) => {
+                return Err(syn::Error::new(name.span, "expected `=` and a value"))
+            }
+            _ => {}
+        }
+        Ok(Attr {
+            kind,
+            span: name.span,
+            value,
+        })
+    }
+}
+
+impl Attr {
+    fn expand(&self) -> TokenStream2 {
+        let b = builder_ident();
+        let value = match &self.value {
+            Some(Value::Lit(lit)) => quote! { #lit },
+            Some(Value::Expr(expr)) => quote! { #expr },
+            None => quote! { true },
+        };
+        let call = match &self.kind {
+            AttrKind::Plain(name) => quote_spanned! {self.span=> .attr(#name, #value) },
+            AttrKind::Classes => match &self.value {
+                Some(Value::Lit(Lit::Str(lit))) => {
+                    let classes = lit.value();
+                    let classes = classes.split_whitespace();
+                    quote_spanned! {self.span=> .classes(&[#(#classes),*]) }
+                }
+                _ => quote_spanned! {self.span=>
+                    .classes(
+                        &::std::convert::AsRef::<str>::as_ref(&(#value))
+                            .split_whitespace()
+                            .collect::<::std::vec::Vec<&str>>()
+                    )
+                },
+            },
+            AttrKind::Class(name) => match &self.value {
+                None => quote_spanned! {self.span=> .class(#name) },
+                Some(_) => quote_spanned! {self.span=> .class_if(#name, #value) },
+            },
+            AttrKind::Style(property) => quote_spanned! {self.span=> .style(#property, #value) },
+            AttrKind::Event(event) => match event.as_str() {
+                "click" => quote_spanned! {self.span=> .on_click(#value) },
+                "keydown" => quote_spanned! {self.span=> .on_keydown(#value) },
+                "input" => quote_spanned! {self.span=> .on_input(#value) },
+                "change" => quote_spanned! {self.span=> .on_change(#value) },
+                "submit" => quote_spanned! {self.span=> .on_submit(#value) },
+                _ => quote_spanned! {self.span=> .on(#event, #value) },
+            },
+        };
+        quote! { let #b = #b #call; }
+    }
+}
+
+/// A child of an element.
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+    If(If),
+    For(For),
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            if input.peek2(Token![/]) {
+                return Err(input.error("unexpected closing tag"));
+            }
+            Ok(Node::Element(input.parse()?))
+        } else if input.peek(LitStr) {
+            Ok(Node::Text(input.parse()?))
+        } else if input.peek(Lit) {
+            Ok(Node::Expr(input.parse()?))
+        } else if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            Ok(Node::Expr(content.parse()?))
+        } else if input.peek(Token![if]) {
+            Ok(Node::If(input.parse()?))
+        } else if input.peek(Token![for]) {
+            Ok(Node::For(input.parse()?))
+        } else if input.peek(Token![else]) {
+            Err(input.error("`else` without an `if` before it"))
+        } else {
+            Err(input.error("expected an element, a string, `{expression}`, `if` or `for`"))
+        }
+    }
+}
+
+/// Parse the nodes of an `if` or `for` body.
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let mut nodes = Vec::new();
+    while !content.is_empty() {
+        nodes.push(content.parse()?);
+    }
+    Ok(nodes)
+}
+
+/// Expand nodes to statements appending them to the builder variable.
+fn expand_nodes(nodes: &[Node], krate: &TokenTree, namespace: Namespace) -> TokenStream2 {
+    let b = builder_ident();
+    let statements = nodes.iter().map(|node| match node {
+        Node::Element(element) => {
+            let child = element.expand(krate, namespace);
+            quote! { let #b = #b.child(#child); }
+        }
+        Node::Text(text) => quote! { let #b = #b.text(#text); },
+        Node::Expr(expr) => quote_spanned! {expr.span()=> let #b = #b.append(#expr); },
+        Node::If(node) => {
+            let node = node.expand(krate, namespace);
+            quote! { let #b = #node; }
+        }
+        Node::For(node) => node.expand(krate, namespace),
+    });
+    quote! { #(#statements)* }
+}
+
+/// `if condition { nodes } else if ... { nodes } else { nodes }`.
+struct If {
+    condition: Expr,
+    then: Vec<Node>,
+    otherwise: Option<Else>,
+}
+
+enum Else {
+    If(Box<If>),
+    Nodes(Vec<Node>),
+}
+
+impl Parse for If {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let condition = Expr::parse_without_eager_brace(input)?;
+        let then = parse_nodes(input)?;
+        let otherwise = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(Else::If(Box::new(input.parse()?)))
+            } else {
+                Some(Else::Nodes(parse_nodes(input)?))
+            }
+        } else {
+            None
+        };
+        Ok(If {
+            condition,
+            then,
+            otherwise,
+        })
+    }
+}
+
+impl If {
+    /// Expand to an `if` expression evaluating to the builder.
+    fn expand(&self, krate: &TokenTree, namespace: Namespace) -> TokenStream2 {
+        let b = builder_ident();
+        let condition = &self.condition;
+        let then = expand_nodes(&self.then, krate, namespace);
+        let otherwise = match &self.otherwise {
+            Some(Else::If(node)) => node.expand(krate, namespace),
+            Some(Else::Nodes(nodes)) => {
+                let nodes = expand_nodes(nodes, krate, namespace);
+                quote! { { #nodes #b } }
+            }
+            None => quote! { { #b } },
+        };
+        quote! {
+            if #condition { #then #b } else #otherwise
+        }
+    }
+}
+
+/// `for pattern in iterator { nodes }`.
+struct For {
+    pat: Pat,
+    iter: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for For {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![in]>()?;
+        let iter = Expr::parse_without_eager_brace(input)?;
+        let body = parse_nodes(input)?;
+        Ok(For { pat, iter, body })
+    }
+}
+
+impl For {
+    /// Expand to a statement appending the body once per item.
+    fn expand(&self, krate: &TokenTree, namespace: Namespace) -> TokenStream2 {
+        let b = builder_ident();
+        let acc = Ident::new("__minimal_acc", Span::mixed_site());
+        let pat = &self.pat;
+        let iter = &self.iter;
+        let body = expand_nodes(&self.body, krate, namespace);
+        quote! {
+            let #b = {
+                let mut #acc = #b;
+                for #pat in #iter {
+                    let #b = #acc;
+                    #body
+                    #acc = #b;
+                }
+                #acc
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Markup;
+
+    /// Expand markup, without spaces between the tokens.
+    fn expand(markup: &str) -> String {
+        let markup: Markup = syn::parse_str(&format!("Minimal; {}", markup)).unwrap();
+        markup.expand().to_string().replace(' ', "")
+    }
+
+    #[test]
+    fn html_elements() {
+        let code = expand(r#"<div class="a b"><p>"text"</p></div>"#);
+        assert!(code.contains(r#"Minimal::el("div")"#));
+        assert!(code.contains(r#"Minimal::el("p")"#));
+        assert!(code.contains(r#".classes(&["a","b"])"#));
+        assert!(!code.contains("Minimal::svg"));
+    }
+
+    #[test]
+    fn namespace_switches_in_svg_and_foreign_object() {
+        let code = expand(
+            r#"<div><svg><g><foreignObject><p><span></span></p></foreignObject></g></svg><em></em></div>"#,
+        );
+        for html in ["div", "p", "span", "em"] {
+            assert!(code.contains(&format!(r#"Minimal::el("{}")"#, html)), "{} is not HTML", html);
+        }
+        // foreignObject itself is SVG, only its children are HTML.
+        for svg in ["svg", "g", "foreignObject"] {
+            assert!(code.contains(&format!(r#"Minimal::svg("{}")"#, svg)), "{} is not SVG", svg);
+        }
+    }
+
+    #[test]
+    fn on_attributes_need_a_known_event() {
+        let code = expand(r#"<details open onclick={|_| ()} onwards="x" on:my-event={|_| ()}></details>"#);
+        assert!(code.contains(r#".attr("open",true)"#));
+        assert!(code.contains(r#".attr("onwards","x")"#));
+        assert!(code.contains(".on_click("));
+        assert!(code.contains(r#".on("my-event","#));
+    }
+
+    #[test]
+    fn namespace_switches_in_math() {
+        let code = expand(r#"<p><math><mi>"x"</mi></math></p>"#);
+        assert!(code.contains(r#"Minimal::el("p")"#));
+        assert!(code.contains(r#"Minimal::mathml("math")"#));
+        assert!(code.contains(r#"Minimal::mathml("mi")"#));
+    }
+}
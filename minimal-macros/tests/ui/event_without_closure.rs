@@ -0,0 +1,3 @@
+fn main() {
+    let _ = Minimal::html!(<button onclick="alert()">"Ok"</button>);
+}
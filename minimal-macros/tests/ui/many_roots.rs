@@ -0,0 +1,3 @@
+fn main() {
+    let _ = Minimal::html!(<p></p><p></p>);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = Minimal::html!(<div><span>"text"</div></span>);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = minimal_macros::html!(<div></div>);
+}
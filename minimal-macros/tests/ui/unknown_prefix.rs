@@ -0,0 +1,3 @@
+fn main() {
+    let _ = Minimal::html!(<div data:id="1"></div>);
+}
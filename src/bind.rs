@@ -0,0 +1,174 @@
+//! Two-way bindings between fields and States.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::event::{EventListenerHandle, MinimalEvents};
+use crate::form::Control;
+use crate::state::{State, Subscription};
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+use web_sys::{Element, HtmlInputElement};
+
+/// A binding from `bind_value()` or `bind_checked()`.
+///
+/// Dropping the handle stops both directions. Call `forget()` to keep it for the
+/// whole life of the page.
+#[must_use = "the binding stops as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct Binding {
+    listener: EventListenerHandle,
+    subscription: Subscription,
+}
+
+impl Binding {
+    /// Keep the binding forever, leaking its listener.
+    pub fn forget(self) {
+        self.listener.forget();
+        self.subscription.forget();
+    }
+}
+
+fn not_a_field(element: &Element, expected: &'static str) -> MinimalError {
+    MinimalError::WrongType {
+        target: Target::Field(element.get_attribute("name").unwrap_or_default()),
+        expected,
+        found: element.node_name(),
+    }
+}
+
+/// Bind the value of an input, a textarea or a select to state, both ways.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// let name = State::new(String::from("Ada"));
+/// bind_value(&document.query_selector_el("input[name=name]"), &name).forget();
+///
+/// let quantity = State::new(1u32);
+/// bind_parsed(&document.query_selector_el("input[name=quantity]"), &quantity).forget();
+/// quantity.update(|quantity| *quantity += 1); // the input shows 2
+/// ```
+pub fn bind_value(element: &Element, state: &State<String>) -> Binding {
+    try_bind_value(element, state).or_panic()
+}
+
+/// Bind the value of a field to state, or an error if element is not a field.
+pub fn try_bind_value(element: &Element, state: &State<String>) -> Result<Binding, MinimalError> {
+    try_bind_value_with(element, state, |text| Some(text.to_owned()), String::clone)
+}
+
+/// Bind the value of a field to a state parsed with FromStr and written with
+/// Display, like a number. The state keeps its value while the text does not parse.
+pub fn bind_parsed<T>(element: &Element, state: &State<T>) -> Binding
+where
+    T: FromStr + fmt::Display + Clone + 'static,
+{
+    try_bind_parsed(element, state).or_panic()
+}
+
+/// Bind the value of a field to a parsed state, or an error if element is not a field.
+pub fn try_bind_parsed<T>(element: &Element, state: &State<T>) -> Result<Binding, MinimalError>
+where
+    T: FromStr + fmt::Display + Clone + 'static,
+{
+    try_bind_value_with(element, state, |text| text.trim().parse().ok(), T::to_string)
+}
+
+/// Bind the value of a field to state, with parse reading the text, `None` while it
+/// is not valid, and format writing it, like a date as `2024-05-01`.
+pub fn bind_value_with<T, P, F>(element: &Element, state: &State<T>, parse: P, format: F) -> Binding
+where
+    T: Clone + 'static,
+    P: Fn(&str) -> Option<T> + 'static,
+    F: Fn(&T) -> String + 'static,
+{
+    try_bind_value_with(element, state, parse, format).or_panic()
+}
+
+/// Bind the value of a field with parse and format, or an error if element is not a
+/// field.
+pub fn try_bind_value_with<T, P, F>(
+    element: &Element,
+    state: &State<T>,
+    parse: P,
+    format: F,
+) -> Result<Binding, MinimalError>
+where
+    T: Clone + 'static,
+    P: Fn(&str) -> Option<T> + 'static,
+    F: Fn(&T) -> String + 'static,
+{
+    let control = Control::new(element.clone())
+        .ok_or_else(|| not_a_field(element, "input, select or textarea"))?;
+    // A select changes at once, the text fields on every key.
+    let event = match control {
+        Control::Select(_) => "change",
+        _ => "input",
+    };
+    state.with(|value| control.set_value(&format(value)));
+    // Set while the field writes the state, so it does not write itself back and
+    // move the caret.
+    let writing = Rc::new(Cell::new(false));
+    let subscription = {
+        let control = control.clone();
+        let writing = writing.clone();
+        state.subscribe(move |value| {
+            let text = format(value);
+            if !writing.get() && control.value() != text {
+                control.set_value(&text);
+            }
+        })
+    };
+    let state = state.clone();
+    let listener = element.try_on(event, move |_| {
+        if let Some(value) = parse(&control.value()) {
+            writing.set(true);
+            state.set(value);
+            writing.set(false);
+        }
+    })?;
+    Ok(Binding { listener, subscription })
+}
+
+/// Bind the checked state of a checkbox to state, both ways.
+///
+/// Radios are not supported: a radio unchecked by picking another one of its group
+/// fires no event, so its state would stay true.
+pub fn bind_checked(element: &Element, state: &State<bool>) -> Binding {
+    try_bind_checked(element, state).or_panic()
+}
+
+/// Bind checked to state, or an error if element is not a checkbox.
+pub fn try_bind_checked(element: &Element, state: &State<bool>) -> Result<Binding, MinimalError> {
+    let input = match Control::new(element.clone()) {
+        Some(Control::Input(input)) if input.type_().eq_ignore_ascii_case("checkbox") => input,
+        Some(Control::Input(input)) => {
+            return Err(MinimalError::WrongType {
+                target: Target::Field(input.name()),
+                expected: "checkbox",
+                found: input.type_(),
+            })
+        }
+        _ => return Err(not_a_field(element, "checkbox")),
+    };
+    input.set_checked(state.get());
+    let subscription = {
+        let input: HtmlInputElement = input.clone();
+        state.subscribe(move |checked| {
+            if input.checked() != *checked {
+                input.set_checked(*checked);
+            }
+        })
+    };
+    let state = state.clone();
+    let listener = element.try_on("change", move |_| {
+        if input.checked() != state.get() {
+            state.set(input.checked());
+        }
+    })?;
+    Ok(Binding { listener, subscription })
+}
@@ -0,0 +1,376 @@
+//! Fluent builder creating DOM trees from Rust.
+
+use crate::error::{cast, MinimalError, OrPanic, Target};
+use crate::event::{EventListenerHandle, MinimalEvents};
+use crate::utils::{document, MinimalElement};
+use std::fmt;
+use std::marker::PhantomData;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    CssStyleDeclaration, Element, Event, HtmlElement, InputEvent, KeyboardEvent, MouseEvent, Node,
+    SubmitEvent,
+};
+
+/// Namespace of SVG elements.
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+/// Namespace of MathML elements.
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// Start building an HTML element.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let dialog = el("div")
+///     .class("card")
+///     .attr("role", "dialog")
+///     .style("display", "flex")
+///     .text("hi")
+///     .child(el("span").text("!"))
+///     .on_click(|_| println!("clicked"))
+///     .build();
+/// Minimal::document().set_new_body(dialog);
+/// ```
+pub fn el(tag: &str) -> ElementBuilder<HtmlElement> {
+    ElementBuilder::new(
+        document()
+            .create_element(tag)
+            .map_err(|e| MinimalError::js(Target::Member("create_element"), e)),
+    )
+}
+
+/// Start building an SVG element, like `svg("circle")`.
+pub fn svg(tag: &str) -> ElementBuilder<Element> {
+    el_ns(SVG_NAMESPACE, tag)
+}
+
+/// Start building a MathML element, like `mathml("mfrac")`.
+pub fn mathml(tag: &str) -> ElementBuilder<Element> {
+    el_ns(MATHML_NAMESPACE, tag)
+}
+
+/// Start building an element of any namespace.
+pub fn el_ns(namespace: &str, tag: &str) -> ElementBuilder<Element> {
+    ElementBuilder::new(
+        document()
+            .create_element_ns(Some(namespace), tag)
+            .map_err(|e| MinimalError::js(Target::Member("create_element_ns"), e)),
+    )
+}
+
+/// An element being built, from `el`, `svg`, `mathml` or `el_ns`.
+///
+/// Every method applies to the element right away. The first error is kept and
+/// returned by `try_build()`, the next methods do nothing.
+///
+/// The listeners added with `on` stay as long as the built element lives, unless
+/// the element is built with `try_build_with_listeners()` to remove them by hand.
+#[must_use = "the element is only returned by build()"]
+#[derive(Debug)]
+pub struct ElementBuilder<T> {
+    element: Result<Element, MinimalError>,
+    listeners: Vec<EventListenerHandle>,
+    marker: PhantomData<T>,
+}
+
+impl<T: JsCast> ElementBuilder<T> {
+    fn new(element: Result<Element, MinimalError>) -> Self {
+        ElementBuilder {
+            element,
+            listeners: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    fn apply<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&Element, &mut Vec<EventListenerHandle>) -> Result<(), MinimalError>,
+    {
+        if let Ok(element) = &self.element {
+            if let Err(e) = f(element, &mut self.listeners) {
+                self.element = Err(e);
+                self.listeners.clear();
+            }
+        }
+        self
+    }
+
+    /// Set the id of the element.
+    pub fn id(self, value: &str) -> Self {
+        self.apply(|element, _| {
+            element.set_id(value);
+            Ok(())
+        })
+    }
+
+    /// Add a class to the element.
+    pub fn class(self, value: &str) -> Self {
+        self.apply(|element, _| element.try_add_class(value))
+    }
+
+    /// Add many classes to the element.
+    pub fn classes(self, values: &[&str]) -> Self {
+        self.apply(|element, _| element.try_add_classes(values))
+    }
+
+    /// Add a class to the element only if `condition` is true.
+    pub fn class_if(self, value: &str, condition: bool) -> Self {
+        if condition {
+            self.class(value)
+        } else {
+            self
+        }
+    }
+
+    /// Set an attribute of the element. A `false` or `None` value leaves it unset.
+    pub fn attr(self, name: &str, value: impl AttrValue) -> Self {
+        self.apply(|element, _| match value.to_attr() {
+            Some(value) => element
+                .set_attribute(name, &value)
+                .map_err(|e| MinimalError::js(Target::attribute(name), e)),
+            None => Ok(()),
+        })
+    }
+
+    /// Set a style property of the element, same as set_prop().
+    pub fn style(self, property: &str, value: impl fmt::Display) -> Self {
+        self.apply(|element, _| {
+            // Read `style` through JavaScript, so it works for SVG and MathML too.
+            js_sys::Reflect::get(element, &JsValue::from_str("style"))
+                .map_err(|e| MinimalError::js(Target::style(property), e))?
+                .unchecked_into::<CssStyleDeclaration>()
+                .set_property(property, &value.to_string())
+                .map_err(|e| MinimalError::js(Target::style(property), e))
+        })
+    }
+
+    /// Append a text node to the element.
+    pub fn text(self, value: &str) -> Self {
+        self.apply(|element, _| {
+            let text = document().create_text_node(value);
+            element.try_app_child(text).map(|_| ())
+        })
+    }
+
+    /// Append a child being built to the element.
+    pub fn child<U>(self, child: ElementBuilder<U>) -> Self {
+        self.apply(|element, listeners| {
+            let child_element = child.element?;
+            element.try_app_child(child_element)?;
+            listeners.extend(child.listeners);
+            Ok(())
+        })
+    }
+
+    /// Append many children being built to the element, like the items of a `map()`
+    /// or an `Option` for a conditional child.
+    pub fn children<U, I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = ElementBuilder<U>>,
+    {
+        for child in children {
+            self = self.child(child);
+        }
+        self
+    }
+
+    /// Append a text, a child being built, a Node, or an `Option` or `Vec` of them.
+    pub fn append(self, child: impl Child) -> Self {
+        child.append_to(self)
+    }
+
+    /// Append an existing Node to the element.
+    pub fn child_node(self, node: &Node) -> Self {
+        self.apply(|element, _| element.try_app_child(node.clone()).map(|_| ()))
+    }
+
+    /// Listen to an event on the element.
+    pub fn on<F: FnMut(Event) + 'static>(self, event: &str, closure: F) -> Self {
+        self.apply(|element, listeners| {
+            listeners.push(element.try_on(event, closure)?);
+            Ok(())
+        })
+    }
+
+    /// Listen to an event cast to any event type, ignoring the events of another type.
+    pub fn on_as<E: JsCast, F: FnMut(E) + 'static>(self, event: &str, mut closure: F) -> Self {
+        self.on(event, move |event: Event| {
+            if let Ok(event) = event.dyn_into::<E>() {
+                closure(event);
+            }
+        })
+    }
+
+    /// Listen to click as MouseEvent.
+    pub fn on_click<F: FnMut(MouseEvent) + 'static>(self, closure: F) -> Self {
+        self.on_as("click", closure)
+    }
+
+    /// Listen to keydown as KeyboardEvent.
+    pub fn on_keydown<F: FnMut(KeyboardEvent) + 'static>(self, closure: F) -> Self {
+        self.on_as("keydown", closure)
+    }
+
+    /// Listen to input as InputEvent.
+    pub fn on_input<F: FnMut(InputEvent) + 'static>(self, closure: F) -> Self {
+        self.on_as("input", closure)
+    }
+
+    /// Listen to change as Event.
+    pub fn on_change<F: FnMut(Event) + 'static>(self, closure: F) -> Self {
+        self.on("change", closure)
+    }
+
+    /// Listen to submit as SubmitEvent.
+    pub fn on_submit<F: FnMut(SubmitEvent) + 'static>(self, closure: F) -> Self {
+        self.on_as("submit", closure)
+    }
+
+    /// Get the built element.
+    pub fn build(self) -> T {
+        self.try_build().or_panic()
+    }
+
+    /// Get the built element, or the first error met while building it.
+    pub fn try_build(self) -> Result<T, MinimalError> {
+        let (element, listeners) = self.try_build_with_listeners()?;
+        for listener in listeners {
+            listener.release();
+        }
+        Ok(element)
+    }
+
+    /// Get the built element and the handles of its listeners, or the first error
+    /// met while building it. Dropping a handle removes its listener.
+    pub fn try_build_with_listeners(self) -> Result<(T, Vec<EventListenerHandle>), MinimalError> {
+        let element = cast(self.element?, || Target::Member("build"))?;
+        Ok((element, self.listeners))
+    }
+}
+
+/// Something that can be appended to an ElementBuilder with `append()`.
+///
+/// Strings and numbers become text nodes, `Option` and `Vec` append what they hold.
+pub trait Child {
+    /// Append self to parent.
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T>;
+}
+
+impl<U> Child for ElementBuilder<U> {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.child(self)
+    }
+}
+
+impl Child for &str {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.text(self)
+    }
+}
+
+impl Child for String {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.text(&self)
+    }
+}
+
+impl Child for &String {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.text(self)
+    }
+}
+
+impl Child for Node {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.child_node(&self)
+    }
+}
+
+impl Child for Element {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.child_node(&self)
+    }
+}
+
+impl Child for HtmlElement {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        parent.child_node(&self)
+    }
+}
+
+impl<C: Child> Child for Option<C> {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        match self {
+            Some(child) => child.append_to(parent),
+            None => parent,
+        }
+    }
+}
+
+impl<C: Child> Child for Vec<C> {
+    fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+        self.into_iter().fold(parent, |parent, child| child.append_to(parent))
+    }
+}
+
+/// A value that can be set as an attribute with `attr()`.
+///
+/// `true` sets an empty attribute, `false` and `None` leave it unset.
+pub trait AttrValue {
+    /// Get the attribute value, or `None` to leave the attribute unset.
+    fn to_attr(&self) -> Option<String>;
+}
+
+impl AttrValue for &str {
+    fn to_attr(&self) -> Option<String> {
+        Some((*self).to_owned())
+    }
+}
+
+impl AttrValue for String {
+    fn to_attr(&self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl AttrValue for &String {
+    fn to_attr(&self) -> Option<String> {
+        Some((*self).clone())
+    }
+}
+
+impl AttrValue for bool {
+    fn to_attr(&self) -> Option<String> {
+        if *self {
+            Some(String::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl<V: AttrValue> AttrValue for Option<V> {
+    fn to_attr(&self) -> Option<String> {
+        self.as_ref().and_then(AttrValue::to_attr)
+    }
+}
+
+macro_rules! impl_display_child {
+    ($($t:ty),*) => {$(
+        impl Child for $t {
+            fn append_to<T: JsCast>(self, parent: ElementBuilder<T>) -> ElementBuilder<T> {
+                parent.text(&self.to_string())
+            }
+        }
+
+        impl AttrValue for $t {
+            fn to_attr(&self) -> Option<String> {
+                Some(self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_display_child!(char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
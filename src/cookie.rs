@@ -0,0 +1,349 @@
+//! Cookies parsed and written in Rust, following RFC 6265.
+
+use crate::error::{MinimalError, Target};
+use crate::url::percent_decode;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The `SameSite` attribute of a cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    /// Sent with every request, the browsers ask for `secure` too.
+    None,
+}
+
+impl fmt::Display for SameSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        })
+    }
+}
+
+/// A cookie to set with `set_cookie()`.
+///
+/// The name and the value are percent-encoded where RFC 6265 does not allow a
+/// character, and `cookies()` decodes them back. A path or a domain holding `;`, `,`
+/// or a control character is an error of `check()` and `try_set_cookie()`, and is
+/// written encoded so it never adds attributes.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::{Cookie, SameSite};
+///
+/// let cookie = Cookie::new("theme", "dark; blue").path("/").max_age(3600).same_site(SameSite::Lax);
+/// assert_eq!(cookie.to_string(), "theme=dark%3B%20blue; Path=/; Max-Age=3600; SameSite=Lax");
+///
+/// let cookies = Minimal::parse_cookies("theme=dark%3B%20blue; auth=\"1\"");
+/// assert_eq!(cookies.get("theme").map(String::as_str), Some("dark; blue"));
+/// assert_eq!(cookies.get("auth").map(String::as_str), Some("1"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// The path the cookie is sent to, the current directory if `None`.
+    pub path: Option<String>,
+    /// The domain the cookie is sent to, the current host only if `None`.
+    pub domain: Option<String>,
+    /// Seconds before the cookie expires, taking over `expires`.
+    pub max_age: Option<i64>,
+    /// When the cookie expires, in seconds since 1970, at the end of the session if
+    /// `None`.
+    pub expires: Option<i64>,
+    pub same_site: Option<SameSite>,
+    /// Only send the cookie over HTTPS.
+    pub secure: bool,
+}
+
+impl Cookie {
+    /// A session cookie with name and value.
+    pub fn new(name: &str, value: &str) -> Self {
+        Cookie {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the path of the cookie.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    /// Set the domain of the cookie.
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_owned());
+        self
+    }
+
+    /// Set the seconds before the cookie expires.
+    pub fn max_age(mut self, seconds: i64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Set when the cookie expires, in seconds since 1970.
+    pub fn expires(mut self, seconds: i64) -> Self {
+        self.expires = Some(seconds);
+        self
+    }
+
+    /// Set when the cookie expires from a JavaScript Date, like
+    /// `js_sys::Date::new_0()` plus some time.
+    pub fn expires_at(self, date: &js_sys::Date) -> Self {
+        self.expires((date.get_time() / 1000.0).floor() as i64)
+    }
+
+    /// Set the SameSite attribute of the cookie.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Only send the cookie over HTTPS.
+    pub fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// Get the cookie removing this one, with the same name, path and domain.
+    pub fn removal(&self) -> Cookie {
+        Cookie {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            domain: self.domain.clone(),
+            max_age: Some(0),
+            expires: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Check that the path and the domain can be written, or a Parse error naming the
+    /// first one holding `;`, `,` or a control character.
+    pub fn check(&self) -> Result<(), MinimalError> {
+        for (member, value) in [("path", &self.path), ("domain", &self.domain)] {
+            if let Some(value) = value.as_deref().filter(|value| !value.bytes().all(is_attribute_octet)) {
+                return Err(MinimalError::Parse {
+                    target: Target::Member(member),
+                    expected: "cookie attribute",
+                    found: value.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", encode(&self.name, is_token), encode(&self.value, is_cookie_octet))?;
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", encode(path, is_attribute_octet))?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", encode(domain, is_attribute_octet))?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", http_date(expires))?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a `Cookie` header or `document.cookie` into names and values, decoded.
+///
+/// When a name repeats, for cookies of different paths, the first one is kept, which
+/// is the one of the longest path.
+pub fn parse_cookies(header: &str) -> HashMap<String, String> {
+    let mut cookies = HashMap::new();
+    for pair in header.split(';') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        // A cookie without `=` has an empty name, as browsers do.
+        let (name, value) = pair.split_once('=').unwrap_or(("", pair));
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        cookies
+            .entry(percent_decode(name.trim()))
+            .or_insert_with(|| percent_decode(value));
+    }
+    cookies
+}
+
+/// A `token` character of RFC 2616, allowed in a cookie name.
+fn is_token(byte: u8) -> bool {
+    byte > 0x20 && byte < 0x7f && !b"()<>@,;:\\\"/[]?={}%".contains(&byte)
+}
+
+/// A `cookie-octet` of RFC 6265, allowed in a cookie value. `%` is encoded too, so
+/// decoding gives back the same value.
+fn is_cookie_octet(byte: u8) -> bool {
+    byte > 0x20 && byte < 0x7f && !b"\",;\\%".contains(&byte)
+}
+
+/// A character allowed in the value of an attribute like `Path`, where `;` would start
+/// another attribute.
+fn is_attribute_octet(byte: u8) -> bool {
+    (0x20..0x7f).contains(&byte) && byte != b';' && byte != b','
+}
+
+fn encode(value: &str, allowed: fn(u8) -> bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if allowed(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Format seconds since 1970 as `Sun, 06 Nov 1994 08:49:37 GMT`, the date of
+/// `Expires`.
+fn http_date(seconds: i64) -> String {
+    const DAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    // A time before 1970 expires the cookie all the same.
+    let seconds = seconds.max(0) as u64;
+    let days = seconds / 86400;
+    let time_of_day = seconds % 86400;
+    // Civil date from the days since 1970-01-01, by Howard Hinnant.
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        DAYS[(days % 7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{http_date, parse_cookies, Cookie, SameSite};
+    use crate::error::{MinimalError, Target};
+
+    #[test]
+    fn values_are_encoded_and_decoded() {
+        let cookie = Cookie::new("my name", "a \"b\";c,d\\e%f\u{e9}");
+        let text = cookie.to_string();
+        assert_eq!(text, "my%20name=a%20%22b%22%3Bc%2Cd%5Ce%25f%C3%A9");
+        let cookies = parse_cookies(&text);
+        assert_eq!(cookies.get("my name").map(String::as_str), Some("a \"b\";c,d\\e%f\u{e9}"));
+    }
+
+    #[test]
+    fn attributes_are_written_in_order() {
+        let cookie = Cookie::new("id", "1")
+            .path("/app")
+            .domain("example.com")
+            .max_age(60)
+            .expires(784111777)
+            .same_site(SameSite::None)
+            .secure();
+        assert_eq!(
+            cookie.to_string(),
+            "id=1; Path=/app; Domain=example.com; Max-Age=60; \
+             Expires=Sun, 06 Nov 1994 08:49:37 GMT; SameSite=None; Secure"
+        );
+        assert_eq!(
+            cookie.removal().to_string(),
+            "id=; Path=/app; Domain=example.com; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn quoted_values_are_unquoted() {
+        let cookies = parse_cookies("a=\"1 2\"; b=\"; c=\"\"");
+        assert_eq!(cookies.get("a").map(String::as_str), Some("1 2"));
+        assert_eq!(cookies.get("b").map(String::as_str), Some("\""));
+        assert_eq!(cookies.get("c").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn first_of_duplicate_names_is_kept() {
+        let cookies = parse_cookies("theme=dark; theme=light; other=1");
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies.get("theme").map(String::as_str), Some("dark"));
+    }
+
+    #[test]
+    fn empty_and_malformed_pairs() {
+        assert!(parse_cookies("").is_empty());
+        assert!(parse_cookies(" ; ;; ").is_empty());
+        let cookies = parse_cookies("novalue; a=; =b; c = 3 ; d=x=y; e=%zz");
+        assert_eq!(cookies.get("").map(String::as_str), Some("novalue"));
+        assert_eq!(cookies.get("a").map(String::as_str), Some(""));
+        assert_eq!(cookies.get("c").map(String::as_str), Some("3"));
+        assert_eq!(cookies.get("d").map(String::as_str), Some("x=y"));
+        assert_eq!(cookies.get("e").map(String::as_str), Some("%zz"));
+    }
+
+    #[test]
+    fn unsafe_path_and_domain_are_rejected_and_encoded() {
+        let cookie = Cookie::new("a", "1").path("/; Domain=evil.com");
+        assert_eq!(
+            cookie.check(),
+            Err(MinimalError::Parse {
+                target: Target::Member("path"),
+                expected: "cookie attribute",
+                found: "/; Domain=evil.com".to_owned(),
+            })
+        );
+        assert_eq!(cookie.to_string(), "a=1; Path=/%3B Domain=evil.com");
+        let cookie = Cookie::new("a", "1").domain("example.com\r\nSet-Cookie: b=2");
+        assert_eq!(cookie.check().unwrap_err().target(), &Target::Member("domain"));
+        assert!(!cookie.to_string().contains('\n'));
+        assert!(Cookie::new("a", "1").domain("a,b").check().is_err());
+        assert_eq!(Cookie::new("a", "1").path("/my docs").domain(".example.com").check(), Ok(()));
+    }
+
+    #[test]
+    fn http_dates() {
+        assert_eq!(http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(http_date(-86400), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(http_date(951782400), "Tue, 29 Feb 2000 00:00:00 GMT");
+        assert_eq!(http_date(1709251199), "Thu, 29 Feb 2024 23:59:59 GMT");
+        assert_eq!(http_date(1709251200), "Fri, 01 Mar 2024 00:00:00 GMT");
+        // 2100 is not a leap year.
+        assert_eq!(http_date(4107542399), "Sun, 28 Feb 2100 23:59:59 GMT");
+        assert_eq!(http_date(4107542400), "Mon, 01 Mar 2100 00:00:00 GMT");
+        assert_eq!(http_date(2147483648), "Tue, 19 Jan 2038 03:14:08 GMT");
+        assert_eq!(http_date(253402300799), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+}
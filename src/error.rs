@@ -0,0 +1,252 @@
+//! Errors returned by the `try_` methods of the Minimal traits.
+
+use std::fmt;
+use std::str::FromStr;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::Node;
+
+/// What a failed lookup was looking for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// A CSS selector, as given to `query_selector` or `closest`.
+    Selector(String),
+    /// The id given to `get_element_by_id`.
+    Id(String),
+    /// A position inside a `NodeList`.
+    Index(u32),
+    /// The name of an attribute.
+    Attribute(String),
+    /// The name of a style property.
+    Style(String),
+    /// A class name, or many separated by spaces.
+    Class(String),
+    /// The name of an event, like `click`.
+    Event(String),
+    /// The name of a route parameter, like `id` for `/users/:id`.
+    Param(String),
+    /// A key of localStorage or sessionStorage, prefix included.
+    Key(String),
+    /// The name of a form field.
+    Field(String),
+    /// A fixed member of a DOM object, such as `parent_element` or `document`.
+    Member(&'static str),
+}
+
+impl Target {
+    pub(crate) fn selector(value: &str) -> Self {
+        Target::Selector(value.to_owned())
+    }
+    pub(crate) fn id(value: &str) -> Self {
+        Target::Id(value.to_owned())
+    }
+    pub(crate) fn attribute(value: &str) -> Self {
+        Target::Attribute(value.to_owned())
+    }
+    pub(crate) fn style(value: &str) -> Self {
+        Target::Style(value.to_owned())
+    }
+    pub(crate) fn class(value: &str) -> Self {
+        Target::Class(value.to_owned())
+    }
+    pub(crate) fn event(value: &str) -> Self {
+        Target::Event(value.to_owned())
+    }
+    pub(crate) fn param(value: &str) -> Self {
+        Target::Param(value.to_owned())
+    }
+    #[cfg(feature = "storage")]
+    pub(crate) fn key(value: &str) -> Self {
+        Target::Key(value.to_owned())
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Selector(value) => write!(f, "selector {:?}", value),
+            Target::Id(value) => write!(f, "id {:?}", value),
+            Target::Index(index) => write!(f, "index {}", index),
+            Target::Attribute(value) => write!(f, "attribute {:?}", value),
+            Target::Style(value) => write!(f, "style property {:?}", value),
+            Target::Class(value) => write!(f, "class {:?}", value),
+            Target::Event(value) => write!(f, "event {:?}", value),
+            Target::Param(value) => write!(f, "route parameter {:?}", value),
+            Target::Key(value) => write!(f, "storage key {:?}", value),
+            Target::Field(value) => write!(f, "form field {:?}", value),
+            Target::Member(name) => write!(f, "`{}`", name),
+        }
+    }
+}
+
+/// Error returned by every `try_` method.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// match document.try_query_selector_html(".container") {
+///     Ok(container) => container.set_prop("display", "none"),
+///     Err(MinimalError::NotFound(target)) => println!("missing {}", target),
+///     Err(e) => println!("{}", e),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinimalError {
+    /// Nothing matched the target.
+    NotFound(Target),
+    /// Something matched, but it is not of the expected type.
+    WrongType {
+        target: Target,
+        /// The type that was asked for, like `HtmlElement`.
+        expected: &'static str,
+        /// The node name of what was found, like `svg` or `#text`.
+        found: String,
+    },
+    /// The browser threw a JavaScript exception.
+    Js {
+        target: Target,
+        /// The exception as `Name: message`, like `SyntaxError: ...`.
+        message: String,
+    },
+    /// A value was read, but it could not be parsed.
+    Parse {
+        target: Target,
+        /// The type that was asked for, like `Length`.
+        expected: &'static str,
+        /// The value that was read, like `auto`.
+        found: String,
+    },
+    /// Nothing happened to the target before the timeout.
+    Timeout {
+        target: Target,
+        /// The timeout, in milliseconds.
+        ms: u32,
+    },
+}
+
+impl MinimalError {
+    /// Get the selector, id or member involved in the error.
+    pub fn target(&self) -> &Target {
+        match self {
+            MinimalError::NotFound(target) => target,
+            MinimalError::WrongType { target, .. } => target,
+            MinimalError::Js { target, .. } => target,
+            MinimalError::Parse { target, .. } => target,
+            MinimalError::Timeout { target, .. } => target,
+        }
+    }
+
+    pub(crate) fn js(target: Target, value: JsValue) -> Self {
+        MinimalError::Js {
+            target,
+            message: js_message(&value),
+        }
+    }
+}
+
+impl fmt::Display for MinimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MinimalError::NotFound(target) => write!(f, "nothing found for {}", target),
+            MinimalError::WrongType {
+                target,
+                expected,
+                found,
+            } => write!(f, "expected {} for {}, found {}", expected, target, found),
+            MinimalError::Js { target, message } => {
+                write!(f, "JavaScript exception for {}: {}", target, message)
+            }
+            MinimalError::Parse {
+                target,
+                expected,
+                found,
+            } => write!(f, "cannot parse {:?} as {} for {}", found, expected, target),
+            MinimalError::Timeout { target, ms } => {
+                write!(f, "timed out after {} ms waiting for {}", ms, target)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MinimalError {}
+
+impl From<MinimalError> for JsValue {
+    fn from(error: MinimalError) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
+
+/// Format a thrown JavaScript value as `Name: message`.
+fn js_message(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        format!("{}: {}", String::from(error.name()), String::from(error.message()))
+    } else if let Some(message) = value.as_string() {
+        message
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// Get the last path segment of a type name, `HtmlElement` for `web_sys::HtmlElement`.
+pub(crate) fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Cast a JavaScript object, reporting the node name of what was found on failure.
+pub(crate) fn cast<T: JsCast>(
+    value: impl JsCast,
+    target: impl FnOnce() -> Target,
+) -> Result<T, MinimalError> {
+    value.dyn_into::<T>().map_err(|value| MinimalError::WrongType {
+        target: target(),
+        expected: short_type_name::<T>(),
+        found: match value.dyn_ref::<Node>() {
+            Some(node) => node.node_name(),
+            None => format!("{:?}", value.as_ref()),
+        },
+    })
+}
+
+/// Parse a value read from the DOM, reporting the value on failure.
+pub(crate) fn parse<T: FromStr>(value: String, target: impl FnOnce() -> Target) -> Result<T, MinimalError> {
+    value.parse().map_err(|_| MinimalError::Parse {
+        target: target(),
+        expected: short_type_name::<T>(),
+        found: value,
+    })
+}
+
+/// Read a JavaScript number, like the `JsValue` of `screen_x()`.
+pub(crate) fn number(value: JsValue, target: impl FnOnce() -> Target) -> Result<f64, MinimalError> {
+    value.as_f64().ok_or_else(|| MinimalError::WrongType {
+        target: target(),
+        expected: "f64",
+        found: format!("{:?}", value),
+    })
+}
+
+/// Cast an optional JavaScript object, keeping `None` as it is.
+pub(crate) fn cast_option<T: JsCast>(
+    value: Option<impl JsCast>,
+    target: impl FnOnce() -> Target,
+) -> Result<Option<T>, MinimalError> {
+    value.map(|value| cast(value, target)).transpose()
+}
+
+/// Panic with the error message, used by the methods without `try_`.
+pub(crate) trait OrPanic<T> {
+    fn or_panic(self) -> T;
+}
+
+impl<T> OrPanic<T> for Result<T, MinimalError> {
+    #[track_caller]
+    fn or_panic(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
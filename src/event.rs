@@ -0,0 +1,326 @@
+//! Event listeners removed when their handle is dropped.
+
+use crate::error::{MinimalError, OrPanic, Target};
+#[cfg(feature = "async")]
+use crate::future::{EventFuture, EventStream};
+use crate::utils::MinimalElement;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    AddEventListenerOptions, Element, Event, EventTarget, FocusEvent, HtmlElement, InputEvent,
+    KeyboardEvent, MouseEvent, Node, SubmitEvent,
+};
+
+/// Options of an event listener, same as the options of `addEventListener`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let window = Minimal::window();
+/// let options = ListenerOptions { passive: true, ..Default::default() };
+/// window.on_with_options("scroll", options, |_| {}).forget();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// Remove the listener after the first event.
+    pub once: bool,
+    /// Promise never to call prevent_default(), so scrolling is not blocked.
+    pub passive: bool,
+    /// Listen during the capture phase instead of the bubble phase.
+    pub capture: bool,
+}
+
+/// A registered event listener.
+///
+/// Dropping the handle removes the listener and frees the wasm Closure.
+/// Call `forget()` to keep the listener for the whole life of the page.
+#[must_use = "the listener is removed as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct EventListenerHandle {
+    target: EventTarget,
+    event: String,
+    capture: bool,
+    closure: Option<Closure<dyn FnMut(Event)>>,
+}
+
+impl EventListenerHandle {
+    /// Get the name of the event listened to.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Keep the listener forever, leaking the Closure.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+
+    /// Keep the listener as long as its target lives, handing the Closure to the
+    /// JavaScript garbage collector.
+    pub fn release(mut self) {
+        if let Some(closure) = self.closure.take() {
+            drop(closure.into_js_value());
+        }
+    }
+}
+
+impl Drop for EventListenerHandle {
+    fn drop(&mut self) {
+        if let Some(closure) = self.closure.take() {
+            let _ = self.target.remove_event_listener_with_callback_and_bool(
+                &self.event,
+                closure.as_ref().unchecked_ref(),
+                self.capture,
+            );
+        }
+    }
+}
+
+/// Some function for listening to events on an Element, a Document or a Window.
+///
+/// The typed shortcuts hand the closure the event already cast, and ignore events
+/// of another type, like a synthetic `Event` dispatched as `click`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// let button = document.query_selector_html("button");
+/// let handle = button.on_click(|event| {
+///     println!("clicked at {}, {}", event.client_x(), event.client_y());
+/// });
+/// // the listener is removed when `handle` is dropped
+/// handle.forget();
+/// ```
+pub trait MinimalEvents {
+    /// Listen to an event, same as add_event_listener().
+    fn on<F: FnMut(Event) + 'static>(&self, event: &str, closure: F) -> EventListenerHandle;
+    /// Listen to an event, or get the JavaScript exception.
+    fn try_on<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        closure: F,
+    ) -> Result<EventListenerHandle, MinimalError>;
+    /// Listen to an event with once, passive or capture options.
+    fn on_with_options<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        options: ListenerOptions,
+        closure: F,
+    ) -> EventListenerHandle;
+    /// Listen to an event with options, or get the JavaScript exception.
+    fn try_on_with_options<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        options: ListenerOptions,
+        closure: F,
+    ) -> Result<EventListenerHandle, MinimalError>;
+    /// Listen to an event cast to any event type, like `WheelEvent`.
+    fn on_as<E: JsCast, F: FnMut(E) + 'static>(&self, event: &str, closure: F) -> EventListenerHandle;
+    /// Listen to click as MouseEvent.
+    fn on_click<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to dblclick as MouseEvent.
+    fn on_dblclick<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to mousedown as MouseEvent.
+    fn on_mousedown<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to mouseup as MouseEvent.
+    fn on_mouseup<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to mousemove as MouseEvent.
+    fn on_mousemove<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to keydown as KeyboardEvent.
+    fn on_keydown<F: FnMut(KeyboardEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to keyup as KeyboardEvent.
+    fn on_keyup<F: FnMut(KeyboardEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to input as InputEvent.
+    fn on_input<F: FnMut(InputEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to change as Event.
+    fn on_change<F: FnMut(Event) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to focus as FocusEvent.
+    fn on_focus<F: FnMut(FocusEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to blur as FocusEvent.
+    fn on_blur<F: FnMut(FocusEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Listen to submit as SubmitEvent.
+    fn on_submit<F: FnMut(SubmitEvent) + 'static>(&self, closure: F) -> EventListenerHandle;
+    /// Wait for the next event, with the `async` feature.
+    #[cfg(feature = "async")]
+    fn event(&self, event: &str) -> EventFuture;
+    /// Wait for the next event, or get the JavaScript exception.
+    #[cfg(feature = "async")]
+    fn try_event(&self, event: &str) -> Result<EventFuture, MinimalError>;
+    /// Get every event as a Stream, with the `async` feature.
+    #[cfg(feature = "async")]
+    fn events(&self, event: &str) -> EventStream;
+    /// Get every event as a Stream, or get the JavaScript exception.
+    #[cfg(feature = "async")]
+    fn try_events(&self, event: &str) -> Result<EventStream, MinimalError>;
+}
+
+impl MinimalEvents for EventTarget {
+    fn on<F: FnMut(Event) + 'static>(&self, event: &str, closure: F) -> EventListenerHandle {
+        self.try_on(event, closure).or_panic()
+    }
+    fn try_on<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        closure: F,
+    ) -> Result<EventListenerHandle, MinimalError> {
+        self.try_on_with_options(event, ListenerOptions::default(), closure)
+    }
+    fn on_with_options<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        options: ListenerOptions,
+        closure: F,
+    ) -> EventListenerHandle {
+        self.try_on_with_options(event, options, closure).or_panic()
+    }
+    fn try_on_with_options<F: FnMut(Event) + 'static>(
+        &self,
+        event: &str,
+        options: ListenerOptions,
+        closure: F,
+    ) -> Result<EventListenerHandle, MinimalError> {
+        let closure = Closure::<dyn FnMut(Event)>::new(closure);
+        let js_options = AddEventListenerOptions::new();
+        js_options.set_once(options.once);
+        js_options.set_passive(options.passive);
+        js_options.set_capture(options.capture);
+        self.add_event_listener_with_callback_and_add_event_listener_options(
+            event,
+            closure.as_ref().unchecked_ref(),
+            &js_options,
+        )
+        .map_err(|e| MinimalError::js(Target::event(event), e))?;
+        Ok(EventListenerHandle {
+            target: self.clone(),
+            event: event.to_owned(),
+            capture: options.capture,
+            closure: Some(closure),
+        })
+    }
+    fn on_as<E: JsCast, F: FnMut(E) + 'static>(&self, event: &str, mut closure: F) -> EventListenerHandle {
+        self.on(event, move |event: Event| {
+            if let Ok(event) = event.dyn_into::<E>() {
+                closure(event);
+            }
+        })
+    }
+    fn on_click<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("click", closure)
+    }
+    fn on_dblclick<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("dblclick", closure)
+    }
+    fn on_mousedown<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("mousedown", closure)
+    }
+    fn on_mouseup<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("mouseup", closure)
+    }
+    fn on_mousemove<F: FnMut(MouseEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("mousemove", closure)
+    }
+    fn on_keydown<F: FnMut(KeyboardEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("keydown", closure)
+    }
+    fn on_keyup<F: FnMut(KeyboardEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("keyup", closure)
+    }
+    fn on_input<F: FnMut(InputEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("input", closure)
+    }
+    fn on_change<F: FnMut(Event) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on("change", closure)
+    }
+    fn on_focus<F: FnMut(FocusEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("focus", closure)
+    }
+    fn on_blur<F: FnMut(FocusEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("blur", closure)
+    }
+    fn on_submit<F: FnMut(SubmitEvent) + 'static>(&self, closure: F) -> EventListenerHandle {
+        self.on_as("submit", closure)
+    }
+    #[cfg(feature = "async")]
+    fn event(&self, event: &str) -> EventFuture {
+        self.try_event(event).or_panic()
+    }
+    #[cfg(feature = "async")]
+    fn try_event(&self, event: &str) -> Result<EventFuture, MinimalError> {
+        EventFuture::new(self, event)
+    }
+    #[cfg(feature = "async")]
+    fn events(&self, event: &str) -> EventStream {
+        self.try_events(event).or_panic()
+    }
+    #[cfg(feature = "async")]
+    fn try_events(&self, event: &str) -> Result<EventStream, MinimalError> {
+        EventStream::new(self, event)
+    }
+}
+
+/// Listen to an event on every descendant of container matching selector, with a
+/// single listener on container.
+///
+/// The closure gets the event and the closest HtmlElement matching selector from the
+/// event target, up to container excluded, so it keeps working when the descendants
+/// are re-rendered.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// let list = document.query_selector_el("ul");
+/// on_delegate(&list, "click", "li.item", |_event, item| {
+///     item.toggle_class("selected");
+/// })
+/// .forget();
+/// ```
+pub fn on_delegate<F>(container: &Element, event: &str, selector: &str, closure: F) -> EventListenerHandle
+where
+    F: FnMut(Event, HtmlElement) + 'static,
+{
+    try_on_delegate(container, event, selector, closure).or_panic()
+}
+
+/// Listen to an event on every descendant of container matching selector, or an
+/// error if the selector is invalid.
+pub fn try_on_delegate<F>(
+    container: &Element,
+    event: &str,
+    selector: &str,
+    mut closure: F,
+) -> Result<EventListenerHandle, MinimalError>
+where
+    F: FnMut(Event, HtmlElement) + 'static,
+{
+    // Fail now on an invalid selector, instead of on every event.
+    container.try_has_match(selector)?;
+    let boundary = container.clone();
+    let selector = selector.to_owned();
+    container.try_on(event, move |event: Event| {
+        let target = match event.target().and_then(|t| t.dyn_into::<Node>().ok()) {
+            Some(node) => node,
+            None => return,
+        };
+        let start = match target.dyn_into::<Element>() {
+            Ok(element) => element,
+            Err(node) => match node.parent_element() {
+                Some(element) => element,
+                None => return,
+            },
+        };
+        if let Ok(Some(matched)) = start.find_closest_html(&selector) {
+            let element: &Element = matched.as_ref();
+            if *element != boundary && boundary.contains(Some(element)) {
+                closure(event, matched);
+            }
+        }
+    })
+}
@@ -0,0 +1,292 @@
+//! Reading and writing the fields of a form, or of any element holding fields.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::url::QueryParams;
+use crate::utils::Queryable;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlFormElement, HtmlInputElement, HtmlOptionElement, HtmlSelectElement, HtmlTextAreaElement};
+
+/// A field found under a form.
+#[derive(Clone)]
+pub(crate) enum Control {
+    Input(HtmlInputElement),
+    Select(HtmlSelectElement),
+    TextArea(HtmlTextAreaElement),
+}
+
+impl Control {
+    pub(crate) fn new(element: Element) -> Option<Control> {
+        let element = match element.dyn_into::<HtmlInputElement>() {
+            Ok(input) => return Some(Control::Input(input)),
+            Err(element) => element,
+        };
+        let element = match element.dyn_into::<HtmlSelectElement>() {
+            Ok(select) => return Some(Control::Select(select)),
+            Err(element) => element,
+        };
+        element.dyn_into::<HtmlTextAreaElement>().ok().map(Control::TextArea)
+    }
+
+    pub(crate) fn element(&self) -> &Element {
+        match self {
+            Control::Input(input) => input,
+            Control::Select(select) => select,
+            Control::TextArea(textarea) => textarea,
+        }
+    }
+
+    pub(crate) fn value(&self) -> String {
+        match self {
+            Control::Input(input) => input.value(),
+            Control::Select(select) => select.value(),
+            Control::TextArea(textarea) => textarea.value(),
+        }
+    }
+
+    pub(crate) fn set_value(&self, value: &str) {
+        match self {
+            Control::Input(input) => input.set_value(value),
+            Control::Select(select) => select.set_value(value),
+            Control::TextArea(textarea) => textarea.set_value(value),
+        }
+    }
+
+    pub(crate) fn set_custom_validity(&self, message: &str) {
+        match self {
+            Control::Input(input) => input.set_custom_validity(message),
+            Control::Select(select) => select.set_custom_validity(message),
+            Control::TextArea(textarea) => textarea.set_custom_validity(message),
+        }
+    }
+
+    pub(crate) fn report_validity(&self) -> bool {
+        match self {
+            Control::Input(input) => input.report_validity(),
+            Control::Select(select) => select.report_validity(),
+            Control::TextArea(textarea) => textarea.report_validity(),
+        }
+    }
+}
+
+/// The kind of an input, by its type attribute.
+#[derive(PartialEq, Eq)]
+enum InputKind {
+    /// A checkbox or a radio, sending its value when checked.
+    Checkable,
+    /// A button or a file, never sent as text.
+    Skipped,
+    Text,
+}
+
+fn input_kind(input: &HtmlInputElement) -> InputKind {
+    match input.type_().to_ascii_lowercase().as_str() {
+        "checkbox" | "radio" => InputKind::Checkable,
+        "submit" | "button" | "reset" | "image" | "file" => InputKind::Skipped,
+        _ => InputKind::Text,
+    }
+}
+
+/// Get the fields under root with a name, in document order.
+pub(crate) fn controls(root: &Element) -> Result<Vec<(String, Control)>, MinimalError> {
+    Ok(root
+        .try_query_selector_list("input[name], select[name], textarea[name]")?
+        .into_iter()
+        .filter_map(|element| {
+            let name = element.get_attribute("name").filter(|name| !name.is_empty())?;
+            Control::new(element).map(|control| (name, control))
+        })
+        .collect())
+}
+
+fn options(select: &HtmlSelectElement) -> Result<Vec<HtmlOptionElement>, MinimalError> {
+    Ok(select
+        .try_query_selector_list("option")?
+        .into_iter()
+        .filter_map(|option| option.dyn_into::<HtmlOptionElement>().ok())
+        .collect())
+}
+
+pub(crate) fn is_disabled(element: &Element) -> bool {
+    // `:disabled` covers the fields of a disabled fieldset too.
+    element.matches(":disabled").unwrap_or(false)
+}
+
+/// Some function for the fields of a form, or of any element holding fields, like a
+/// fieldset or a dialog.
+///
+/// The values are the ones the form would submit: checkboxes and radios only when
+/// checked, every selected option of a multi-select, and nothing for disabled
+/// fields, buttons and files.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let form = Minimal::document().query_selector_el("form#search");
+/// let mut values = form.values();
+/// println!("{:?} {}", values.get("q"), form.to_url_encoded());
+/// values.set("page", 1);
+/// form.fill(&values);
+/// form.reset();
+/// ```
+pub trait MinimalForm {
+    /// Get the names and values of the fields, in document order.
+    fn values(&self) -> QueryParams;
+    /// Get the names and values of the fields, or the JavaScript exception.
+    fn try_values(&self) -> Result<QueryParams, MinimalError>;
+    /// Set the fields from values. A checkbox, a radio or an option is checked if
+    /// its value is among the values of its name, and fields sharing a name get the
+    /// values in order. The fields whose name is missing are left as they are, hidden
+    /// and disabled ones included, so an empty value is the way to clear a field.
+    fn fill(&self, values: &QueryParams);
+    /// Set the fields from values, or the JavaScript exception.
+    fn try_fill(&self, values: &QueryParams) -> Result<(), MinimalError>;
+    /// Get the fields deserialized into T, as `QueryParams::to_struct()` does.
+    #[cfg(feature = "serde")]
+    fn to_struct<T: serde::de::DeserializeOwned>(&self) -> T;
+    /// Get the fields deserialized into T, or an error if they do not fit T.
+    #[cfg(feature = "serde")]
+    fn try_to_struct<T: serde::de::DeserializeOwned>(&self) -> Result<T, MinimalError>;
+    /// Set the fields from a struct, as `QueryParams::from_struct()` writes it.
+    #[cfg(feature = "serde")]
+    fn fill_from<T: serde::Serialize + ?Sized>(&self, value: &T);
+    /// Set the fields from a struct, or an error if it is not a struct of values.
+    #[cfg(feature = "serde")]
+    fn try_fill_from<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<(), MinimalError>;
+    /// Get the fields as `application/x-www-form-urlencoded`, like `a=1&b=two+words`.
+    fn to_url_encoded(&self) -> String;
+    /// Get the fields url-encoded, or the JavaScript exception.
+    fn try_to_url_encoded(&self) -> Result<String, MinimalError>;
+    /// Put the fields back to their values in the HTML, same as reset() of a form.
+    fn reset(&self);
+    /// Put the fields back to their values in the HTML, or the JavaScript exception.
+    fn try_reset(&self) -> Result<(), MinimalError>;
+}
+
+impl MinimalForm for Element {
+    fn values(&self) -> QueryParams {
+        self.try_values().or_panic()
+    }
+    fn try_values(&self) -> Result<QueryParams, MinimalError> {
+        let mut values = QueryParams::new();
+        for (name, control) in controls(self)? {
+            match control {
+                Control::Input(input) => {
+                    if is_disabled(&input) {
+                        continue;
+                    }
+                    match input_kind(&input) {
+                        InputKind::Checkable if input.checked() => values.append(&name, input.value()),
+                        InputKind::Text => values.append(&name, input.value()),
+                        _ => {}
+                    }
+                }
+                Control::Select(select) => {
+                    if is_disabled(&select) {
+                        continue;
+                    }
+                    for option in options(&select)? {
+                        if option.selected() && !is_disabled(&option) {
+                            values.append(&name, option.value());
+                        }
+                    }
+                }
+                Control::TextArea(textarea) => {
+                    if !is_disabled(&textarea) {
+                        values.append(&name, textarea.value());
+                    }
+                }
+            }
+        }
+        Ok(values)
+    }
+    fn fill(&self, values: &QueryParams) {
+        self.try_fill(values).or_panic()
+    }
+    fn try_fill(&self, values: &QueryParams) -> Result<(), MinimalError> {
+        // How many fields of each name already got a value.
+        let mut used: HashMap<String, usize> = HashMap::new();
+        for (name, control) in controls(self)? {
+            let all = values.get_all(&name);
+            if all.is_empty() {
+                continue;
+            }
+            let mut next = || {
+                let index = used.entry(name.clone()).or_insert(0);
+                *index += 1;
+                all.get(*index - 1).copied().unwrap_or_default()
+            };
+            match control {
+                Control::Input(input) => match input_kind(&input) {
+                    InputKind::Checkable => {
+                        let value = input.value();
+                        let checkbox = input.type_().eq_ignore_ascii_case("checkbox");
+                        input.set_checked(all.iter().any(|v| *v == value || (checkbox && *v == "true")));
+                    }
+                    InputKind::Text => input.set_value(next()),
+                    InputKind::Skipped => {}
+                },
+                Control::Select(select) => {
+                    for option in options(&select)? {
+                        option.set_selected(all.contains(&option.value().as_str()));
+                    }
+                }
+                Control::TextArea(textarea) => textarea.set_value(next()),
+            }
+        }
+        Ok(())
+    }
+    #[cfg(feature = "serde")]
+    fn to_struct<T: serde::de::DeserializeOwned>(&self) -> T {
+        self.try_to_struct().or_panic()
+    }
+    #[cfg(feature = "serde")]
+    fn try_to_struct<T: serde::de::DeserializeOwned>(&self) -> Result<T, MinimalError> {
+        self.try_values()?.to_struct()
+    }
+    #[cfg(feature = "serde")]
+    fn fill_from<T: serde::Serialize + ?Sized>(&self, value: &T) {
+        self.try_fill_from(value).or_panic()
+    }
+    #[cfg(feature = "serde")]
+    fn try_fill_from<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<(), MinimalError> {
+        self.try_fill(&QueryParams::from_struct(value)?)
+    }
+    fn to_url_encoded(&self) -> String {
+        self.try_to_url_encoded().or_panic()
+    }
+    fn try_to_url_encoded(&self) -> Result<String, MinimalError> {
+        Ok(self.try_values()?.to_string())
+    }
+    fn reset(&self) {
+        self.try_reset().or_panic()
+    }
+    fn try_reset(&self) -> Result<(), MinimalError> {
+        if let Some(form) = self.dyn_ref::<HtmlFormElement>() {
+            form.reset();
+            return Ok(());
+        }
+        for (name, control) in controls(self)? {
+            match control {
+                Control::Input(input) => match input_kind(&input) {
+                    InputKind::Checkable => input.set_checked(input.default_checked()),
+                    _ => input.set_value(&input.default_value()),
+                },
+                Control::Select(select) => {
+                    for option in options(&select)? {
+                        option.set_selected(option.default_selected());
+                    }
+                }
+                Control::TextArea(textarea) => {
+                    let value = textarea
+                        .default_value()
+                        .map_err(|e| MinimalError::js(Target::Field(name), e))?;
+                    textarea.set_value(&value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
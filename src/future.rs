@@ -0,0 +1,341 @@
+//! Futures and streams over timers, frames and events, with the `async` feature.
+//!
+//! Each future holds the timer or the listener it waits for, so dropping it before
+//! it is ready cancels the timer or removes the listener.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::event::{EventListenerHandle, MinimalEvents};
+use crate::timer::{AnimationFrame, Timeout};
+use crate::utils::{document, window, MinimalWindow};
+use futures_core::Stream;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use wasm_bindgen::prelude::*;
+use web_sys::{Event, EventTarget, MutationObserver, MutationObserverInit, Node};
+
+/// Values sent by a callback to the future or the stream waiting for them.
+#[derive(Debug)]
+struct Signal<T> {
+    queue: VecDeque<T>,
+    waker: Option<Waker>,
+}
+
+type SharedSignal<T> = Rc<RefCell<Signal<T>>>;
+
+fn signal<T>() -> SharedSignal<T> {
+    Rc::new(RefCell::new(Signal {
+        queue: VecDeque::new(),
+        waker: None,
+    }))
+}
+
+fn send<T>(signal: &SharedSignal<T>, value: T) {
+    let mut signal = signal.borrow_mut();
+    signal.queue.push_back(value);
+    if let Some(waker) = signal.waker.take() {
+        waker.wake();
+    }
+}
+
+fn receive<T>(signal: &SharedSignal<T>, cx: &mut Context<'_>) -> Poll<T> {
+    let mut signal = signal.borrow_mut();
+    match signal.queue.pop_front() {
+        Some(value) => Poll::Ready(value),
+        None => {
+            signal.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Wait for ms milliseconds, same as set_timeout().
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// spawn_local(async {
+///     document_ready().await;
+///     let button = Minimal::document().query_selector_el("button");
+///     loop {
+///         button.event("click").await;
+///         button.add_class("busy");
+///         sleep(1000).await;
+///         button.remove_class("busy");
+///     }
+/// });
+/// ```
+pub fn sleep(ms: u32) -> Sleep {
+    let signal = signal();
+    let sender = signal.clone();
+    let timeout = window().set_timeout(ms, move || send(&sender, ()));
+    Sleep { signal, _timeout: timeout }
+}
+
+/// Future of `sleep()`, cancelling its timer when dropped.
+#[must_use = "futures do nothing unless awaited"]
+#[derive(Debug)]
+pub struct Sleep {
+    signal: SharedSignal<()>,
+    _timeout: Timeout,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        receive(&self.signal, cx)
+    }
+}
+
+/// Wait for the next frame, getting its timestamp, same as request_animation_frame().
+pub fn next_frame() -> NextFrame {
+    let signal = signal();
+    let sender = signal.clone();
+    let frame = window().request_frame(move |time| send(&sender, time));
+    NextFrame { signal, _frame: frame }
+}
+
+/// Future of `next_frame()`, cancelling its frame when dropped.
+#[must_use = "futures do nothing unless awaited"]
+#[derive(Debug)]
+pub struct NextFrame {
+    signal: SharedSignal<f64>,
+    _frame: AnimationFrame,
+}
+
+impl Future for NextFrame {
+    type Output = f64;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<f64> {
+        receive(&self.signal, cx)
+    }
+}
+
+/// Future of `event()`, ready with the first event and removing its listener when
+/// dropped.
+#[must_use = "futures do nothing unless awaited"]
+#[derive(Debug)]
+pub struct EventFuture {
+    signal: SharedSignal<Event>,
+    listener: Option<EventListenerHandle>,
+}
+
+impl EventFuture {
+    pub(crate) fn new(target: &EventTarget, event: &str) -> Result<Self, MinimalError> {
+        let signal = signal();
+        let sender = signal.clone();
+        let listener = target.try_on(event, move |event| send(&sender, event))?;
+        Ok(EventFuture {
+            signal,
+            listener: Some(listener),
+        })
+    }
+}
+
+impl Future for EventFuture {
+    type Output = Event;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Event> {
+        let poll = receive(&self.signal, cx);
+        if poll.is_ready() {
+            self.listener = None;
+        }
+        poll
+    }
+}
+
+/// Stream of `events()`, never ending, removing its listener when dropped.
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct EventStream {
+    signal: SharedSignal<Event>,
+    _listener: EventListenerHandle,
+}
+
+impl EventStream {
+    pub(crate) fn new(target: &EventTarget, event: &str) -> Result<Self, MinimalError> {
+        let signal = signal();
+        let sender = signal.clone();
+        let listener = target.try_on(event, move |event| send(&sender, event))?;
+        Ok(EventStream { signal, _listener: listener })
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        receive(&self.signal, cx).map(Some)
+    }
+}
+
+/// Wait for the document to be parsed, same as `DOMContentLoaded`, ready right away
+/// if it already is.
+pub fn document_ready() -> Ready {
+    let document = document();
+    if document.ready_state() != "loading" {
+        return Ready { event: None };
+    }
+    let target: &EventTarget = document.as_ref();
+    Ready {
+        event: Some(EventFuture::new(target, "DOMContentLoaded").or_panic()),
+    }
+}
+
+/// Wait for the page and its images and styles to be loaded, same as `load`, ready
+/// right away if they already are.
+pub fn window_loaded() -> Ready {
+    if document().ready_state() == "complete" {
+        return Ready { event: None };
+    }
+    let window = window();
+    let target: &EventTarget = window.as_ref();
+    Ready {
+        event: Some(EventFuture::new(target, "load").or_panic()),
+    }
+}
+
+/// Future of `document_ready()` and `window_loaded()`.
+#[must_use = "futures do nothing unless awaited"]
+#[derive(Debug)]
+pub struct Ready {
+    event: Option<EventFuture>,
+}
+
+impl Future for Ready {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &mut self.event {
+            Some(event) => Pin::new(event).poll(cx).map(|_| ()),
+            None => Poll::Ready(()),
+        }
+    }
+}
+
+/// The observer and the timer of a pending `WaitFor`.
+struct Watch {
+    observer: MutationObserver,
+    _closure: Closure<dyn FnMut()>,
+    _timeout: Timeout,
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Future of `wait_for_selector()` and `wait_for_removal()`, ready with an error
+/// if the timeout comes first. Dropping it disconnects its MutationObserver.
+#[must_use = "futures do nothing unless awaited"]
+pub struct WaitFor<T> {
+    signal: SharedSignal<Result<T, MinimalError>>,
+    watch: Option<Watch>,
+}
+
+impl<T> std::fmt::Debug for WaitFor<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitFor")
+            .field("pending", &self.watch.is_some())
+            .finish()
+    }
+}
+
+impl<T: 'static> WaitFor<T> {
+    /// Wait for check to give a value, trying again on every change under root.
+    pub(crate) fn new<F>(root: &Node, target: Target, ms: u32, mut check: F) -> Self
+    where
+        F: FnMut() -> Result<Option<T>, MinimalError> + 'static,
+    {
+        let signal = signal();
+        let watch = match check() {
+            Ok(Some(value)) => {
+                send(&signal, Ok(value));
+                None
+            }
+            Err(error) => {
+                send(&signal, Err(error));
+                None
+            }
+            Ok(None) => match WaitFor::watch(root, target, ms, &signal, check) {
+                Ok(watch) => Some(watch),
+                Err(error) => {
+                    send(&signal, Err(error));
+                    None
+                }
+            },
+        };
+        WaitFor { signal, watch }
+    }
+
+    fn watch<F>(
+        root: &Node,
+        target: Target,
+        ms: u32,
+        signal: &SharedSignal<Result<T, MinimalError>>,
+        mut check: F,
+    ) -> Result<Watch, MinimalError>
+    where
+        F: FnMut() -> Result<Option<T>, MinimalError> + 'static,
+    {
+        let sender = signal.clone();
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            // Only the first result counts, the observer is disconnected right after.
+            if !sender.borrow().queue.is_empty() {
+                return;
+            }
+            match check() {
+                Ok(Some(value)) => send(&sender, Ok(value)),
+                Ok(None) => {}
+                Err(error) => send(&sender, Err(error)),
+            }
+        });
+        let observer = MutationObserver::new(closure.as_ref().unchecked_ref())
+            .map_err(|e| MinimalError::js(Target::Member("MutationObserver"), e))?;
+        let options = MutationObserverInit::new();
+        options.set_child_list(true);
+        options.set_subtree(true);
+        // The selector may match on an attribute or a class.
+        options.set_attributes(true);
+        observer
+            .observe_with_options(root, &options)
+            .map_err(|e| MinimalError::js(Target::Member("observe"), e))?;
+        let sender = signal.clone();
+        let timeout = window().try_set_timeout(ms, move || {
+            if sender.borrow().queue.is_empty() {
+                send(&sender, Err(MinimalError::Timeout { target, ms }));
+            }
+        });
+        let timeout = match timeout {
+            Ok(timeout) => timeout,
+            Err(error) => {
+                observer.disconnect();
+                return Err(error);
+            }
+        };
+        Ok(Watch {
+            observer,
+            _closure: closure,
+            _timeout: timeout,
+        })
+    }
+}
+
+impl<T> Future for WaitFor<T> {
+    type Output = Result<T, MinimalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let poll = receive(&self.signal, cx);
+        if poll.is_ready() {
+            self.watch = None;
+        }
+        poll
+    }
+}
@@ -0,0 +1,305 @@
+//! Rectangles and sizes read from the layout, with the maths done in Rust.
+
+use web_sys::{DomRect, DomRectReadOnly};
+
+/// A width and a height in CSS pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Size {
+    /// A size of width by height.
+    pub fn new(width: f64, height: f64) -> Self {
+        Size { width, height }
+    }
+
+    /// Get width times height.
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+/// A rectangle in CSS pixels, like the DOMRect of getBoundingClientRect().
+///
+/// It is read through its accessors, `x()` and `y()` being the same as `left()` and
+/// `top()`, kept for both habits. They are methods rather than public fields, so the
+/// edges are always computed from the position and the size, and cannot disagree
+/// after a change to one of them.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::Rect;
+///
+/// let viewport = Rect::new(0.0, 0.0, 800.0, 600.0);
+/// let card = Rect::new(700.0, 500.0, 200.0, 200.0);
+/// assert_eq!(card.intersection(&viewport), Some(Rect::new(700.0, 500.0, 100.0, 100.0)));
+/// assert_eq!(card.visible_ratio(&viewport), 0.25);
+/// assert!(card.is_visible_in(&viewport, 0.25));
+/// assert_eq!(card.right(), 900.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    /// A rectangle at x, y of width by height. A negative size is made positive, as
+    /// DOMRect does.
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        let (left, width) = if width < 0.0 { (x + width, -width) } else { (x, width) };
+        let (top, height) = if height < 0.0 { (y + height, -height) } else { (y, height) };
+        Rect {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// A rectangle from its edges.
+    pub fn from_edges(left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        Rect::new(left, top, right - left, bottom - top)
+    }
+
+    /// Get the left edge, same as `left()`.
+    pub fn x(&self) -> f64 {
+        self.left
+    }
+
+    /// Get the top edge, same as `top()`.
+    pub fn y(&self) -> f64 {
+        self.top
+    }
+
+    /// Get the width, never negative.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// Get the height, never negative.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Get the left edge.
+    pub fn left(&self) -> f64 {
+        self.left
+    }
+
+    /// Get the top edge.
+    pub fn top(&self) -> f64 {
+        self.top
+    }
+
+    /// Get the right edge, left plus width.
+    pub fn right(&self) -> f64 {
+        self.left + self.width
+    }
+
+    /// Get the bottom edge, top plus height.
+    pub fn bottom(&self) -> f64 {
+        self.top + self.height
+    }
+
+    /// Get the size of the rectangle.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Get width times height.
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Get the center of the rectangle.
+    pub fn center(&self) -> (f64, f64) {
+        (self.left + self.width / 2.0, self.top + self.height / 2.0)
+    }
+
+    /// Get the rectangle moved by dx, dy.
+    pub fn translate(&self, dx: f64, dy: f64) -> Rect {
+        Rect::new(self.left + dx, self.top + dy, self.width, self.height)
+    }
+
+    /// Check if the point x, y is inside the rectangle, edges included.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.left && x <= self.right() && y >= self.top && y <= self.bottom()
+    }
+
+    /// Check if other is fully inside the rectangle.
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.left >= self.left
+            && other.right() <= self.right()
+            && other.top >= self.top
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Check if the rectangles overlap, touching edges included.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.left <= other.right() && other.left <= self.right() && self.top <= other.bottom() && other.top <= self.bottom()
+    }
+
+    /// Get the overlap of the rectangles, or `None` if they do not touch.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect::from_edges(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        ))
+    }
+
+    /// Get the part of the rectangle inside viewport, from 0 to 1.
+    ///
+    /// An empty rectangle is 1 when it touches viewport and 0 otherwise.
+    pub fn visible_ratio(&self, viewport: &Rect) -> f64 {
+        match self.intersection(viewport) {
+            Some(_) if self.area() == 0.0 => 1.0,
+            Some(visible) => visible.area() / self.area(),
+            None => 0.0,
+        }
+    }
+
+    /// Check if at least threshold of the rectangle, from 0 to 1, is inside viewport.
+    /// A threshold of 0 means any part of it, even an edge.
+    pub fn is_visible_in(&self, viewport: &Rect, threshold: f64) -> bool {
+        if threshold <= 0.0 {
+            self.intersects(viewport)
+        } else {
+            self.visible_ratio(viewport) >= threshold
+        }
+    }
+}
+
+impl From<DomRect> for Rect {
+    fn from(rect: DomRect) -> Self {
+        Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+    }
+}
+
+impl From<&DomRect> for Rect {
+    fn from(rect: &DomRect) -> Self {
+        Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+    }
+}
+
+impl From<DomRectReadOnly> for Rect {
+    fn from(rect: DomRectReadOnly) -> Self {
+        Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+    }
+}
+
+impl From<&DomRectReadOnly> for Rect {
+    fn from(rect: &DomRectReadOnly) -> Self {
+        Rect::new(rect.x(), rect.y(), rect.width(), rect.height())
+    }
+}
+
+/// Every metric of a Window read at once, from `metrics()`, so a frame of layout code
+/// works on one consistent reading.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let metrics = Minimal::window().metrics();
+/// let physical_width = metrics.inner.width * metrics.device_pixel_ratio;
+/// let bottom = metrics.scroll_y + metrics.viewport.bottom();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowMetrics {
+    /// inner_width and inner_height, scrollbars included.
+    pub inner: Size,
+    /// outer_width and outer_height, the whole browser window.
+    pub outer: Size,
+    /// Position of the browser window on the screen.
+    pub screen_x: f64,
+    pub screen_y: f64,
+    /// Scroll of the document.
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+    /// Physical pixels per CSS pixel.
+    pub device_pixel_ratio: f64,
+    /// The visual viewport, relative to the layout viewport.
+    pub viewport: Rect,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rect;
+
+    #[test]
+    fn negative_size_is_made_positive() {
+        let rect = Rect::new(10.0, 10.0, -4.0, -6.0);
+        assert_eq!(rect, Rect::new(6.0, 4.0, 4.0, 6.0));
+        assert_eq!((rect.x(), rect.y(), rect.right(), rect.bottom()), (6.0, 4.0, 10.0, 10.0));
+        assert_eq!(Rect::from_edges(1.0, 2.0, 4.0, 8.0), Rect::new(1.0, 2.0, 3.0, 6.0));
+    }
+
+    #[test]
+    fn disjoint_rects() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rect::new(20.0, 0.0, 10.0, 10.0);
+        assert!(!a.intersects(&b));
+        assert_eq!(a.intersection(&b), None);
+        assert_eq!(b.visible_ratio(&a), 0.0);
+        assert!(!b.is_visible_in(&a, 0.0));
+    }
+
+    #[test]
+    fn touching_edges_intersect_with_zero_area() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rect::new(10.0, 0.0, 10.0, 10.0);
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&b), Some(Rect::new(10.0, 0.0, 0.0, 10.0)));
+        assert_eq!(b.visible_ratio(&a), 0.0);
+        assert!(b.is_visible_in(&a, 0.0));
+        assert!(!b.is_visible_in(&a, 0.01));
+    }
+
+    #[test]
+    fn contained_rect() {
+        let outer = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let inner = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert!(outer.contains(&inner));
+        assert!(!inner.contains(&outer));
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        assert_eq!(inner.visible_ratio(&outer), 1.0);
+        assert_eq!(outer.visible_ratio(&inner), 0.12);
+        assert!(outer.contains_point(100.0, 100.0));
+        assert!(!outer.contains_point(100.5, 0.0));
+    }
+
+    #[test]
+    fn zero_area_rect() {
+        let viewport = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let point = Rect::new(50.0, 50.0, 0.0, 0.0);
+        let line = Rect::new(0.0, 50.0, 200.0, 0.0);
+        assert_eq!(point.area(), 0.0);
+        assert_eq!(viewport.intersection(&point), Some(point));
+        // 0 / 0 would be NaN, an empty rect touching the viewport counts as visible.
+        assert_eq!(point.visible_ratio(&viewport), 1.0);
+        assert_eq!(line.visible_ratio(&viewport), 1.0);
+        assert_eq!(Rect::new(500.0, 500.0, 0.0, 0.0).visible_ratio(&viewport), 0.0);
+        assert!(point.is_visible_in(&viewport, 1.0));
+        assert_eq!(viewport.visible_ratio(&point), 0.0);
+    }
+
+    #[test]
+    fn center_and_translate() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(rect.center(), (25.0, 40.0));
+        assert_eq!(rect.translate(-10.0, 5.0), Rect::new(0.0, 25.0, 30.0, 40.0));
+        assert_eq!(rect.size().area(), rect.area());
+    }
+}
@@ -1,7 +1,84 @@
 //! # minimal
 //! `minimal` is a collection of utilities to get Element and HtmlElement
 //!  more convenient and easier.
+//!
+//! Every trait method that can fail has a `try_` counterpart returning
+//! `Result<T, MinimalError>` instead of panicking, and every lookup has a
+//! `find_` counterpart returning `Result<Option<T>, MinimalError>`, so a missing
+//! element is `Ok(None)` while an invalid selector is still an error.
+//!
+//! The `_as` methods, like `query_as::<HtmlInputElement>()`, cast to any web_sys
+//! type. The extra element types are enabled by the cargo features `input`,
+//! `select`, `textarea`, `button`, `form`, `anchor`, `image`, `canvas`, `media`
+//! and `svg`, or all of them with `elements`.
+//!
+//! The `macros` feature adds `html!`, writing the element builder as markup.
+//! The `serde` feature reads the query of the url into your own types, and the
+//! `storage` feature adds typed localStorage and sessionStorage in `storage`.
+//! The `form` feature reads and writes the fields of forms with `MinimalForm`, and
+//! with `serde` maps them to your own types. It also validates them with
+//! `Validator`, and binds them to a `State` both ways.
+//! The `async` feature adds futures for timers, frames, events and page loading,
+//! with `spawn_local()` to run them.
+#![allow(non_snake_case)]
 
+#[cfg(feature = "form")]
+mod bind;
+mod builder;
+mod cookie;
+mod error;
+mod event;
+#[cfg(feature = "form")]
+mod form;
+#[cfg(feature = "async")]
+mod future;
+mod geometry;
+mod list;
+mod observer;
+mod route;
+mod router;
+mod state;
+#[cfg(feature = "storage")]
+pub mod storage;
+mod style;
+mod timer;
+mod url;
+#[cfg(feature = "form")]
+mod validation;
+#[cfg(feature = "serde")]
+mod values;
+
+#[cfg(feature = "form")]
+pub use bind::{
+    bind_checked, bind_parsed, bind_value, bind_value_with, try_bind_checked, try_bind_parsed, try_bind_value,
+    try_bind_value_with, Binding,
+};
+pub use builder::{el, el_ns, mathml, svg, AttrValue, Child, ElementBuilder, MATHML_NAMESPACE, SVG_NAMESPACE};
+pub use cookie::{parse_cookies, Cookie, SameSite};
+pub use error::MinimalError;
+pub use error::Target;
+pub use event::{on_delegate, try_on_delegate, EventListenerHandle, ListenerOptions, MinimalEvents};
+#[cfg(feature = "async")]
+pub use future::{document_ready, next_frame, sleep, window_loaded, EventFuture, EventStream, NextFrame, Ready, Sleep, WaitFor};
+#[cfg(feature = "async")]
+pub use wasm_bindgen_futures::spawn_local;
+#[cfg(feature = "form")]
+pub use form::MinimalForm;
+pub use geometry::{Rect, Size, WindowMetrics};
+pub use list::{Classes, ElementList, Elements, Nodes, TypedNodes};
+pub use observer::{
+    observe_intersection, observe_mutations, observe_resize, try_observe_intersection, try_observe_mutations,
+    try_observe_resize, IntersectionEntry, IntersectionObserverHandle, IntersectionOptions, Mutation,
+    MutationObserverHandle, MutationOptions, ResizeEntry, ResizeObserverHandle,
+};
+pub use route::{Params, Route};
+pub use router::{Navigation, Router, RouterMode};
+pub use state::{State, Subscription};
+pub use style::{Color, Display, Length, ParseCssError, Transform, TransformFn};
+pub use timer::{AnimationFrame, FrameLoop, Interval, Timeout};
+pub use url::{QueryParams, Url};
+#[cfg(feature = "form")]
+pub use validation::{FieldError, Rule, ValidationReport, Validator};
 pub use utils::document;
 pub use utils::window;
 pub use utils::MinimalDocument;
@@ -10,20 +87,71 @@ pub use utils::MinimalHtml;
 pub use utils::MinimalList;
 pub use utils::MinimalWindow;
 pub use utils::MinimalNode;
+pub use utils::Queryable;
+pub use web_sys;
+
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use minimal_macros::html as __html;
+
+/// Build an element from JSX-like markup, with the `macros` feature.
+///
+/// It expands to the element builder: `class="a b"` adds classes, `class:name={bool}`
+/// adds a class if true, `style:property=value` sets a style, `onclick={closure}` and
+/// `on` followed by any other DOM event add listeners, `on:name={closure}` listens
+/// to any event, and any other attribute, like `open`, goes through `attr()`.
+/// Strings are text, `{expr}` appends anything implementing `Child`, and `if` and
+/// `for` work between the tags. It panics like `build()`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let items = vec!["one", "two"];
+/// let selected = true;
+/// let list: web_sys::HtmlElement = html! {
+///     <ul class="list" class:selected={selected} aria-label="items">
+///         for item in &items {
+///             <li style:color="red" onclick={move |_| println!("clicked")}>{*item}</li>
+///         }
+///         if items.is_empty() {
+///             <li>"Nothing here"</li>
+///         }
+///     </ul>
+/// };
+/// Minimal::document().set_new_body(list);
+/// ```
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! html {
+    ($($markup:tt)*) => {
+        $crate::__html!($crate; $($markup)*)
+    };
+}
 pub mod utils {
+    use crate::error::{cast, cast_option, number, parse, MinimalError, OrPanic, Target};
+    use crate::cookie::{parse_cookies, Cookie};
+    #[cfg(feature = "async")]
+    use crate::future::WaitFor;
+    use crate::geometry::{Rect, Size, WindowMetrics};
+    use crate::list::{Classes, ElementList, Nodes, TypedNodes};
+    use crate::timer::{AnimationFrame, FrameLoop, Interval, Timeout};
+    use crate::url::{QueryParams, Url};
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::str::FromStr;
     use wasm_bindgen::prelude::*;
-    use web_sys::{Location, Attr, Document, Element, HtmlElement, HtmlSlotElement, NodeList, Window, Node};
+    use web_sys::{Location, Attr, Document, DocumentFragment, Element, HtmlDocument, HtmlElement, HtmlSlotElement, NodeList, ShadowRoot, Window, Node};
     /// Create a Window.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let window = Minimal::window();
     ///
     /// assert_eq!(<Window>, window);
     /// ```
-    ///
-
     pub fn window() -> Window {
         web_sys::window().expect("no window found")
     }
@@ -32,60 +160,118 @@ pub mod utils {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let document = Minimal::document();
     ///
     /// assert_eq!(<Document>, document);
-    ///
-
+    /// ```
     pub fn document() -> Document {
         web_sys::window().expect("no window found").document_page()
     }
 
+    //TRAIT
+
     /// Some function for a Window element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let window = Minimal::window();
     /// let document = window.document_page();
     ///
     /// assert_eq!(<Document>, document);
     /// ```
-
-    //TRAIT
     pub trait MinimalWindow {
         /// Get document of window.
         fn document_page(&self) -> Document;
+        /// Get document of window, or an error if there is none.
+        fn try_document_page(&self) -> Result<Document, MinimalError>;
         /// Get document_element as Element.
         fn document_element_el(&self) -> Element;
+        /// Get document_element as Element, or an error if there is none.
+        fn try_document_element_el(&self) -> Result<Element, MinimalError>;
         /// Get document_element as HtmlElement.
         fn document_element_html(&self) -> HtmlElement;
+        /// Get document_element as HtmlElement, or an error if there is none.
+        fn try_document_element_html(&self) -> Result<HtmlElement, MinimalError>;
         /// Get name of window, same as name() function.
         fn get_name(&self) -> String;
+        /// Get name of window, or the JavaScript exception.
+        fn try_get_name(&self) -> Result<String, MinimalError>;
         /// Get scroll_x of window, same as scroll_x() function.
         fn get_scroll_x(&self) -> f64;
+        /// Get scroll_x of window, or the JavaScript exception.
+        fn try_get_scroll_x(&self) -> Result<f64, MinimalError>;
         /// Get scroll_y of window, same as scroll_y() function.
         fn get_scroll_y(&self) -> f64;
+        /// Get scroll_y of window, or the JavaScript exception.
+        fn try_get_scroll_y(&self) -> Result<f64, MinimalError>;
         /// Get page_x_offset of window, same as page_x_offset() function.
         fn get_page_x_offset(&self) -> f64;
+        /// Get page_x_offset of window, or the JavaScript exception.
+        fn try_get_page_x_offset(&self) -> Result<f64, MinimalError>;
         /// Get page_y_offset of window, same as page_y_offset() function.
         fn get_page_y_offset(&self) -> f64;
+        /// Get page_y_offset of window, or the JavaScript exception.
+        fn try_get_page_y_offset(&self) -> Result<f64, MinimalError>;
         /// Get screen_x of window, same as screen_x() function.
-        fn get_screen_x(&self) -> JsValue;
+        fn get_screen_x(&self) -> f64;
+        /// Get screen_x of window, or the JavaScript exception.
+        fn try_get_screen_x(&self) -> Result<f64, MinimalError>;
         /// Get screen_y of window, same as screen_y() function.
-        fn get_screen_y(&self) -> JsValue;
+        fn get_screen_y(&self) -> f64;
+        /// Get screen_y of window, or the JavaScript exception.
+        fn try_get_screen_y(&self) -> Result<f64, MinimalError>;
         /// Get outer_width of window, same as outer_width() function.
-        fn get_outer_width(&self) -> JsValue;
+        fn get_outer_width(&self) -> f64;
+        /// Get outer_width of window, or the JavaScript exception.
+        fn try_get_outer_width(&self) -> Result<f64, MinimalError>;
         /// Get outer_height of window, same as outer_height() function.
-        fn get_outer_height(&self) -> JsValue;
+        fn get_outer_height(&self) -> f64;
+        /// Get outer_height of window, or the JavaScript exception.
+        fn try_get_outer_height(&self) -> Result<f64, MinimalError>;
+        /// Get inner_width and inner_height of window, scrollbars included.
+        fn inner_size(&self) -> Size;
+        /// Get inner_width and inner_height of window, or the JavaScript exception.
+        fn try_inner_size(&self) -> Result<Size, MinimalError>;
+        /// Get device_pixel_ratio of window, the physical pixels per CSS pixel.
+        fn device_pixel_ratio(&self) -> f64;
+        /// Get the visual viewport as Rect, the part of the page visible after a pinch
+        /// zoom or with the on-screen keyboard open.
+        fn viewport(&self) -> Rect;
+        /// Get the visual viewport as Rect, or an error if the browser has none.
+        fn try_viewport(&self) -> Result<Rect, MinimalError>;
+        /// Read every metric of window at once.
+        fn metrics(&self) -> WindowMetrics;
+        /// Read every metric of window at once, or the first JavaScript exception.
+        fn try_metrics(&self) -> Result<WindowMetrics, MinimalError>;
+        /// Call closure once after ms milliseconds, same as set_timeout(). Dropping the
+        /// handle cancels it.
+        fn set_timeout<F: FnOnce() + 'static>(&self, ms: u32, closure: F) -> Timeout;
+        /// Call closure once after ms milliseconds, or get the JavaScript exception.
+        fn try_set_timeout<F: FnOnce() + 'static>(&self, ms: u32, closure: F) -> Result<Timeout, MinimalError>;
+        /// Call closure every ms milliseconds, same as set_interval(). Dropping the handle
+        /// stops it.
+        fn set_interval<F: FnMut() + 'static>(&self, ms: u32, closure: F) -> Interval;
+        /// Call closure every ms milliseconds, or get the JavaScript exception.
+        fn try_set_interval<F: FnMut() + 'static>(&self, ms: u32, closure: F) -> Result<Interval, MinimalError>;
+        /// Call closure before the next repaint with its timestamp, same as
+        /// request_animation_frame(). Dropping the handle cancels it.
+        fn request_frame<F: FnOnce(f64) + 'static>(&self, closure: F) -> AnimationFrame;
+        /// Call closure before the next repaint, or get the JavaScript exception.
+        fn try_request_frame<F: FnOnce(f64) + 'static>(&self, closure: F) -> Result<AnimationFrame, MinimalError>;
+        /// Call closure on every frame with the milliseconds since the previous one.
+        /// Dropping the handle stops it.
+        fn frame_loop<F: FnMut(f64) + 'static>(&self, closure: F) -> FrameLoop;
+        /// Call closure on every frame, or get the JavaScript exception.
+        fn try_frame_loop<F: FnMut(f64) + 'static>(&self, closure: F) -> Result<FrameLoop, MinimalError>;
     }
 
     /// Some function for a Document element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let window = Minimal::window();
     /// let document = window.document_page();
     /// let h1_el = document.query_selector_el("h1");
@@ -93,187 +279,450 @@ pub mod utils {
     /// assert_eq!(<Element>, h1_el);
     /// assert_eq!(<HtmlElement>, h1_html);
     /// ```
-
     pub trait MinimalDocument {
         /// Get element by id as Element.
-        fn get_element_by_id_el<'a>(&self, value: &'a str) -> Element;
+        fn get_element_by_id_el(&self, value: &str) -> Element;
+        /// Get element by id as Element, or an error if there is none.
+        fn try_get_element_by_id_el(&self, value: &str) -> Result<Element, MinimalError>;
         /// Get element by id as HtmlElement.
-        fn get_element_by_id_html<'a>(&self, value: &'a str) -> HtmlElement;
-        /// Get a List of all elements by selector.
-        fn query_selector_list<'a>(&self, value: &'a str) -> NodeList;
-        /// Get element by selector as Element.
-        fn query_selector_el<'a>(&self, value: &'a str) -> Element;
-        /// Get element by selector as HtmlElement.
-        fn query_selector_html<'a>(&self, value: &'a str) -> HtmlElement;
+        fn get_element_by_id_html(&self, value: &str) -> HtmlElement;
+        /// Get element by id as HtmlElement, or an error if there is none.
+        fn try_get_element_by_id_html(&self, value: &str) -> Result<HtmlElement, MinimalError>;
+        /// Get element by id as Element, `None` if there is none.
+        fn find_element_by_id_el(&self, value: &str) -> Result<Option<Element>, MinimalError>;
+        /// Get element by id as HtmlElement, `None` if there is none.
+        fn find_element_by_id_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError>;
+        /// Get element by id as any element type, like `HtmlInputElement`.
+        fn get_by_id_as<T: JsCast>(&self, value: &str) -> T;
+        /// Get element by id as any element type, or an error if there is none or it has another type.
+        fn try_get_by_id_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError>;
+        /// Get element by id as any element type, `None` if there is none.
+        fn find_by_id_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError>;
         /// Get document_element as Element.
         fn document_element_el(&self) -> Element;
+        /// Get document_element as Element, or an error if there is none.
+        fn try_document_element_el(&self) -> Result<Element, MinimalError>;
         /// Get document_element as HtmlElement.
         fn document_element_html(&self) -> HtmlElement;
+        /// Get document_element as HtmlElement, or an error if there is none.
+        fn try_document_element_html(&self) -> Result<HtmlElement, MinimalError>;
         /// Get url of document, same as url().
         fn get_url(&self) -> String;
+        /// Get url of document, or the JavaScript exception.
+        fn try_get_url(&self) -> Result<String, MinimalError>;
         /// Get location of document, same as location().
         fn get_location(&self) -> Location;
+        /// Get location of document, or an error if there is none.
+        fn try_get_location(&self) -> Result<Location, MinimalError>;
         /// Get hash of document, same as hash().
         fn get_hash(&self) -> String;
+        /// Get hash of document, or an error.
+        fn try_get_hash(&self) -> Result<String, MinimalError>;
         /// Get host of document, same as host().
         fn get_host(&self) -> String;
+        /// Get host of document, or an error.
+        fn try_get_host(&self) -> Result<String, MinimalError>;
         /// Get hostname of document, same as hostname().
         fn get_hostname(&self) -> String;
+        /// Get hostname of document, or an error.
+        fn try_get_hostname(&self) -> Result<String, MinimalError>;
         /// Get href of document, same as href().
         fn get_href(&self) -> String;
+        /// Get href of document, or an error.
+        fn try_get_href(&self) -> Result<String, MinimalError>;
+        /// Get the query of the url as QueryParams, same as search().
+        fn query_params(&self) -> QueryParams;
+        /// Get the query of the url as QueryParams, or an error.
+        fn try_query_params(&self) -> Result<QueryParams, MinimalError>;
+        /// Get the query of the url deserialized with serde, like a struct of filters, as
+        /// `QueryParams::to_struct()` does.
+        #[cfg(feature = "serde")]
+        fn query<T: serde::de::DeserializeOwned>(&self) -> T;
+        /// Get the query of the url deserialized, or an error if it does not fit T.
+        #[cfg(feature = "serde")]
+        fn try_query<T: serde::de::DeserializeOwned>(&self) -> Result<T, MinimalError>;
+        /// Set the query of the url without reloading the page, same as replace_state().
+        fn set_query(&self, query: &QueryParams);
+        /// Set the query of the url without reloading, or the JavaScript exception.
+        fn try_set_query(&self, query: &QueryParams) -> Result<(), MinimalError>;
+        /// Set the hash of the url without reloading the page nor scrolling, same as
+        /// replace_state().
+        fn set_hash(&self, hash: &str);
+        /// Set the hash of the url without reloading, or the JavaScript exception.
+        fn try_set_hash(&self, hash: &str) -> Result<(), MinimalError>;
+        /// Go to url, keeping the page in the history, same as assign().
+        fn assign(&self, url: &str);
+        /// Go to url keeping the page in the history, or the JavaScript exception.
+        fn try_assign(&self, url: &str) -> Result<(), MinimalError>;
+        /// Go to url in place of the page in the history, same as replace().
+        fn replace(&self, url: &str);
+        /// Go to url in place of the page, or the JavaScript exception.
+        fn try_replace(&self, url: &str) -> Result<(), MinimalError>;
+        /// Reload the page, same as reload().
+        fn reload(&self);
+        /// Reload the page, or the JavaScript exception.
+        fn try_reload(&self) -> Result<(), MinimalError>;
+        /// Get the cookies of document by name, decoded.
+        fn cookies(&self) -> HashMap<String, String>;
+        /// Get the cookies of document, or an error if it is not an HTML document.
+        fn try_cookies(&self) -> Result<HashMap<String, String>, MinimalError>;
+        /// Set a cookie of document.
+        fn set_cookie(&self, cookie: &Cookie);
+        /// Set a cookie of document, or an error if it is not an HTML document or the
+        /// path or the domain of cookie holds `;`, `,` or a control character.
+        fn try_set_cookie(&self, cookie: &Cookie) -> Result<(), MinimalError>;
+        /// Remove the cookie with the name, path and domain of cookie.
+        fn remove_cookie(&self, cookie: &Cookie);
+        /// Remove a cookie of document, or an error if it is not an HTML document.
+        fn try_remove_cookie(&self, cookie: &Cookie) -> Result<(), MinimalError>;
         /// Set body of document, same as set_body().
         fn set_new_body(&self, e: HtmlElement);
         /// Get default_view of document, same as default_view().
         fn get_default_view(&self) -> Window;
+        /// Get default_view of document, or an error if there is none.
+        fn try_get_default_view(&self) -> Result<Window, MinimalError>;
     }
 
     /// Some function for a Element element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let document = Minimal::document();
     /// let h1_el = document.query_selector_el("h1");
     /// assert_eq!(<Element>, h1_el);
     /// assert_eq!(<HtmlElement>, h1_el.to_html());
     /// ```
-
     pub trait MinimalElement {
         /// Convert Element to HtmlElement.
         fn to_html(&self) -> HtmlElement;
-        /// Match if element has a class.
-        fn has_class<'a>(&self, value: &'a str) -> bool;
+        /// Convert Element to HtmlElement, or an error if it is not one.
+        fn try_to_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Match if element has a class, same as class_list().contains().
+        fn has_class(&self, value: &str) -> bool;
         /// Add a class to Element.
-        fn add_class<'a>(&self, value: &'a str);
+        fn add_class(&self, value: &str);
+        /// Add a class to Element, or an error if it is not a valid class.
+        fn try_add_class(&self, value: &str) -> Result<(), MinimalError>;
+        /// Add many classes to Element.
+        fn add_classes(&self, values: &[&str]);
+        /// Add many classes to Element, or an error if one is not a valid class.
+        fn try_add_classes(&self, values: &[&str]) -> Result<(), MinimalError>;
         /// Remove a class to Element.
-        fn remove_class<'a>(&self, value: &'a str);
+        fn remove_class(&self, value: &str);
+        /// Remove a class to Element, or an error if it is not a valid class.
+        fn try_remove_class(&self, value: &str) -> Result<(), MinimalError>;
+        /// Remove many classes to Element.
+        fn remove_classes(&self, values: &[&str]);
+        /// Remove many classes to Element, or an error if one is not a valid class.
+        fn try_remove_classes(&self, values: &[&str]) -> Result<(), MinimalError>;
+        /// Replace a class of Element, returns false if Element did not have it.
+        fn replace_class(&self, old: &str, new: &str) -> bool;
+        /// Replace a class of Element, or an error if one is not a valid class.
+        fn try_replace_class(&self, old: &str, new: &str) -> Result<bool, MinimalError>;
         /// Toggle a class to Element.
-        fn toggle_class<'a>(&self, value: &'a str);
+        fn toggle_class(&self, value: &str);
+        /// Toggle a class to Element, or an error if it is not a valid class.
+        fn try_toggle_class(&self, value: &str) -> Result<(), MinimalError>;
+        /// Add the class to Element if `force` is true, remove it otherwise.
+        fn toggle_class_to(&self, value: &str, force: bool);
+        /// Add or remove the class to Element, or an error if it is not a valid class.
+        fn try_toggle_class_to(&self, value: &str, force: bool) -> Result<(), MinimalError>;
+        /// Iterate over the classes of Element.
+        fn classes(&self) -> Classes;
         /// Get namespace_uri, same as namespace_uri().
         fn get_namespace_uri(&self) -> String;
+        /// Get namespace_uri, or an error if there is none.
+        fn try_get_namespace_uri(&self) -> Result<String, MinimalError>;
         /// Get prefix, same as prefix().
         fn get_pref(&self) -> String;
+        /// Get prefix, or an error if there is none.
+        fn try_get_pref(&self) -> Result<String, MinimalError>;
         /// Get assigned_slot, same as assigned_slot().
         fn get_assigned_slot(&self) -> HtmlSlotElement;
-        /// Get a List of all elements by selector.
-        fn query_selector_list<'a>(&self, value: &'a str) -> NodeList;
-        /// Get element by selector as Element.
-        fn query_selector_el<'a>(&self, value: &'a str) -> Element;
-        /// Get element by selector as HtmlElement.
-        fn query_selector_html<'a>(&self, value: &'a str) -> HtmlElement;
+        /// Get assigned_slot, or an error if there is none.
+        fn try_get_assigned_slot(&self) -> Result<HtmlSlotElement, MinimalError>;
         /// Get parent_element as Element.
         fn parent_element_el(&self) -> Element;
+        /// Get parent_element as Element, or an error if there is none.
+        fn try_parent_element_el(&self) -> Result<Element, MinimalError>;
+        /// Get parent_element as Element, `None` if there is none.
+        fn find_parent_element_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get parent_element as HtmlElement.
         fn parent_element_html(&self) -> HtmlElement;
+        /// Get parent_element as HtmlElement, or an error if there is none.
+        fn try_parent_element_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get parent_element as HtmlElement, `None` if there is none.
+        fn find_parent_element_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get first_child as Element.
         fn first_child_el(&self) -> Element;
+        /// Get first_child as Element, or an error if there is none.
+        fn try_first_child_el(&self) -> Result<Element, MinimalError>;
+        /// Get first_child as Element, `None` if there is none.
+        fn find_first_child_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get first_child as HtmlElement.
         fn first_child_html(&self) -> HtmlElement;
+        /// Get first_child as HtmlElement, or an error if there is none.
+        fn try_first_child_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get first_child as HtmlElement, `None` if there is none.
+        fn find_first_child_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get last_child as Element.
         fn last_child_el(&self) -> Element;
+        /// Get last_child as Element, or an error if there is none.
+        fn try_last_child_el(&self) -> Result<Element, MinimalError>;
+        /// Get last_child as Element, `None` if there is none.
+        fn find_last_child_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get last_child as HtmlElement.
         fn last_child_html(&self) -> HtmlElement;
+        /// Get last_child as HtmlElement, or an error if there is none.
+        fn try_last_child_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get last_child as HtmlElement, `None` if there is none.
+        fn find_last_child_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get previous_sibling as Element.
         fn prev_sibling_el(&self) -> Element;
+        /// Get previous_sibling as Element, or an error if there is none.
+        fn try_prev_sibling_el(&self) -> Result<Element, MinimalError>;
+        /// Get previous_sibling as Element, `None` if there is none.
+        fn find_prev_sibling_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get previous_sibling as HtmlElement.
         fn prev_sibling_html(&self) -> HtmlElement;
+        /// Get previous_sibling as HtmlElement, or an error if there is none.
+        fn try_prev_sibling_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get previous_sibling as HtmlElement, `None` if there is none.
+        fn find_prev_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get previous_element_sibling as Element.
         fn prev_element_sibling_el(&self) -> Element;
+        /// Get previous_element_sibling as Element, or an error if there is none.
+        fn try_prev_element_sibling_el(&self) -> Result<Element, MinimalError>;
+        /// Get previous_element_sibling as Element, `None` if there is none.
+        fn find_prev_element_sibling_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get next_element_sibling as Element.
         fn next_element_sibling_el(&self) -> Element;
+        /// Get next_element_sibling as Element, or an error if there is none.
+        fn try_next_element_sibling_el(&self) -> Result<Element, MinimalError>;
+        /// Get next_element_sibling as Element, `None` if there is none.
+        fn find_next_element_sibling_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get first_element_child as Element.
         fn first_element_child_el(&self) -> Element;
+        /// Get first_element_child as Element, or an error if there is none.
+        fn try_first_element_child_el(&self) -> Result<Element, MinimalError>;
+        /// Get first_element_child as Element, `None` if there is none.
+        fn find_first_element_child_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get last_element_child as Element.
         fn last_element_child_el(&self) -> Element;
+        /// Get last_element_child as Element, or an error if there is none.
+        fn try_last_element_child_el(&self) -> Result<Element, MinimalError>;
+        /// Get last_element_child as Element, `None` if there is none.
+        fn find_last_element_child_el(&self) -> Result<Option<Element>, MinimalError>;
         /// Get previous_element_sibling as HtmlElement.
         fn prev_element_sibling_html(&self) -> HtmlElement;
+        /// Get previous_element_sibling as HtmlElement, or an error if there is none.
+        fn try_prev_element_sibling_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get previous_element_sibling as HtmlElement, `None` if there is none.
+        fn find_prev_element_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get next_element_sibling as HtmlElement.
         fn next_element_sibling_html(&self) -> HtmlElement;
+        /// Get next_element_sibling as HtmlElement, or an error if there is none.
+        fn try_next_element_sibling_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get next_element_sibling as HtmlElement, `None` if there is none.
+        fn find_next_element_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get first_element_child as HtmlElement.
         fn first_element_child_html(&self) -> HtmlElement;
+        /// Get first_element_child as HtmlElement, or an error if there is none.
+        fn try_first_element_child_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get first_element_child as HtmlElement, `None` if there is none.
+        fn find_first_element_child_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get last_element_child as HtmlElement.
         fn last_element_child_html(&self) -> HtmlElement;
+        /// Get last_element_child as HtmlElement, or an error if there is none.
+        fn try_last_element_child_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Get last_element_child as HtmlElement, `None` if there is none.
+        fn find_last_element_child_html(&self) -> Result<Option<HtmlElement>, MinimalError>;
         /// Get closest element as Element.
-        fn closest_el<'a>(&self, value: &'a str) -> Element;
+        fn closest_el(&self, value: &str) -> Element;
+        /// Get closest element as Element, or an error if there is none.
+        fn try_closest_el(&self, value: &str) -> Result<Element, MinimalError>;
+        /// Get closest element as Element, `None` if there is none.
+        fn find_closest_el(&self, value: &str) -> Result<Option<Element>, MinimalError>;
         /// Get closest element as HtmlElement.
-        fn closest_html<'a>(&self, value: &'a str) -> HtmlElement;
+        fn closest_html(&self, value: &str) -> HtmlElement;
+        /// Get closest element as HtmlElement, or an error if there is none.
+        fn try_closest_html(&self, value: &str) -> Result<HtmlElement, MinimalError>;
+        /// Get closest element as HtmlElement, `None` if there is none.
+        fn find_closest_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError>;
+        /// Get closest element as any element type, like `HtmlFormElement`.
+        fn closest_as<T: JsCast>(&self, value: &str) -> T;
+        /// Get closest element as any element type, or an error if there is none or it has another type.
+        fn try_closest_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError>;
+        /// Get closest element as any element type, `None` if there is none.
+        fn find_closest_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError>;
         /// Get attribute of Element.
-        fn get_attr<'a>(&self, value: &'a str) -> String;
+        fn get_attr(&self, value: &str) -> String;
+        /// Get attribute of Element, or an error if it is not set.
+        fn try_get_attr(&self, value: &str) -> Result<String, MinimalError>;
         /// Get attribute node of Element.
-        fn get_attr_node<'a>(&self, value: &'a str) -> Attr;
+        fn get_attr_node(&self, value: &str) -> Attr;
+        /// Get attribute node of Element, or an error if it is not set.
+        fn try_get_attr_node(&self, value: &str) -> Result<Attr, MinimalError>;
         /// Get attribute ns of Element.
-        fn get_attr_ns<'a>(&self, namespace: &'a str, localname: &'a str) -> String;
+        fn get_attr_ns(&self, namespace: &str, localname: &str) -> String;
+        /// Get attribute ns of Element, or an error if it is not set.
+        fn try_get_attr_ns(&self, namespace: &str, localname: &str) -> Result<String, MinimalError>;
         /// Insert adjacent element to Element.
-        fn insert_adj_el<'a, 's>(&self, where_: &'s str, element: Element) -> Element;
+        fn insert_adj_el(&self, where_: &str, element: Element) -> Element;
+        /// Insert adjacent element to Element, or get the error.
+        fn try_insert_adj_el(&self, where_: &str, element: Element) -> Result<Element, MinimalError>;
         /// Toggle attribute to Element.
-        fn toggle_attr<'a>(&self, value: &'a str) -> bool;
+        fn toggle_attr(&self, value: &str) -> bool;
+        /// Toggle attribute to Element, or get the JavaScript exception.
+        fn try_toggle_attr(&self, value: &str) -> Result<bool, MinimalError>;
         /// Remove attribute to Element.
-        fn remove_attr<'a>(&self, value: &'a str);
+        fn remove_attr(&self, value: &str);
+        /// Remove attribute to Element, or get the JavaScript exception.
+        fn try_remove_attr(&self, value: &str) -> Result<(), MinimalError>;
         /// Match Element with selector, same as matches().
-        fn has_match<'a>(&self, value: &'a str) -> bool;
+        fn has_match(&self, value: &str) -> bool;
+        /// Match Element with selector, or an error if the selector is invalid.
+        fn try_has_match(&self, value: &str) -> Result<bool, MinimalError>;
         /// Get node value of Element.
         fn get_node_value(&self) -> String;
+        /// Get node value of Element, or an error if there is none.
+        fn try_get_node_value(&self) -> Result<String, MinimalError>;
         /// Get text content of Element.
         fn get_text_content(&self) -> String;
-        /// Append a Node to Element, same as append_child().
-        fn app_child(&self, node: Node) -> Node;
+        /// Get text content of Element, or an error if there is none.
+        fn try_get_text_content(&self) -> Result<String, MinimalError>;
+        /// Append a Node, or an Element, to Element, same as append_child().
+        fn app_child(&self, node: impl Into<Node>) -> Node;
+        /// Append a Node, or an Element, to Element, or get the JavaScript exception.
+        fn try_app_child(&self, node: impl Into<Node>) -> Result<Node, MinimalError>;
+        /// Get the rectangle of Element relative to the viewport, same as
+        /// get_bounding_client_rect().
+        fn rect(&self) -> Rect;
+        /// Get the position of Element relative to the document, adding the scroll of
+        /// the window.
+        fn page_offset(&self) -> (f64, f64);
+        /// Get the position of Element relative to the document, or an error if it has no window.
+        fn try_page_offset(&self) -> Result<(f64, f64), MinimalError>;
+        /// Get the size of Element inside its borders, without the scrollbars.
+        fn client_size(&self) -> Size;
+        /// Get the size of the whole content of Element, scrolled out included.
+        fn scroll_size(&self) -> Size;
+        /// Check if at least threshold of Element, from 0 to 1, is inside the viewport.
+        fn is_in_viewport(&self, threshold: f64) -> bool;
+        /// Check if Element is inside the viewport, or an error if there is no document.
+        fn try_is_in_viewport(&self, threshold: f64) -> Result<bool, MinimalError>;
+        /// Get the overlap of Element with other, or `None` if they do not touch.
+        fn intersection_with(&self, other: &Element) -> Option<Rect>;
     }
 
     /// Some function for a HtmlElement element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let document = Minimal::document();
     /// let h1_html = document.query_selector_html("h1");
     /// if h1_html.has_class("test"){
     ///     h1_html.set_prop("display", "none");
     /// }
     /// ```
-
     pub trait MinimalHtml {
         /// Convert HtmlElement to Element.
         fn to_el(&self) -> Element;
+        /// Convert HtmlElement to Element, or an error if it is not one.
+        fn try_to_el(&self) -> Result<Element, MinimalError>;
         /// Get offset parent as Element.
         fn offset_parent_el(&self) -> Element;
+        /// Get offset parent as Element, or an error if there is none.
+        fn try_offset_parent_el(&self) -> Result<Element, MinimalError>;
         /// Get offset parent as HtmlElement.
         fn offset_parent_html(&self) -> HtmlElement;
-        /// Set property to Element, same as set_property().
-        fn set_prop<'a, 's>(&self, property: &'a str, value: &'s str);
+        /// Get offset parent as HtmlElement, or an error if there is none.
+        fn try_offset_parent_html(&self) -> Result<HtmlElement, MinimalError>;
+        /// Set property to Element, same as set_property(). The value can be a string
+        /// or a typed value like `Length::Px(10.0)` or `Display::None`.
+        fn set_prop(&self, property: &str, value: impl fmt::Display);
+        /// Set property to Element, or get the JavaScript exception.
+        fn try_set_prop(&self, property: &str, value: impl fmt::Display) -> Result<(), MinimalError>;
+        /// Set many properties to Element at once.
+        fn set_styles(&self, styles: &[(&str, &dyn fmt::Display)]);
+        /// Set many properties to Element, or get the first JavaScript exception.
+        fn try_set_styles(&self, styles: &[(&str, &dyn fmt::Display)]) -> Result<(), MinimalError>;
         /// Get property of Element, same as get_property().
-        fn get_prop<'a>(&self, property: &'a str) -> String;
+        fn get_prop(&self, property: &str) -> String;
+        /// Get property of Element, or get the JavaScript exception.
+        fn try_get_prop(&self, property: &str) -> Result<String, MinimalError>;
+        /// Remove property of Element and get its old value, same as remove_property().
+        fn remove_prop(&self, property: &str) -> String;
+        /// Remove property of Element, or get the JavaScript exception.
+        fn try_remove_prop(&self, property: &str) -> Result<String, MinimalError>;
+        /// Get computed property of Element, same as getComputedStyle().
+        fn computed_prop(&self, property: &str) -> String;
+        /// Get computed property of Element, or an error if there is no computed style.
+        fn try_computed_prop(&self, property: &str) -> Result<String, MinimalError>;
+        /// Get computed property of Element parsed as Length, Color, Display, Transform
+        /// or any FromStr type.
+        fn computed_as<T: FromStr>(&self, property: &str) -> T;
+        /// Get computed property of Element parsed, or an error if it cannot be parsed.
+        fn try_computed_as<T: FromStr>(&self, property: &str) -> Result<T, MinimalError>;
     }
 
     /// Some function for a NodeList element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let document = Minimal::document();
     /// let h1_list = document.query_selector_list("h1");
     /// assert_eq!(<NodeList>, h1_list);
     /// let element = h1_list.get_html(5);
     /// assert_eq!(<HtmlElement>, element);
     /// ```
-    ///
     pub trait MinimalList {
         /// Get Node of a List.
         fn get_node(&self, index: u32) -> Node;
+        /// Get Node of a List, or an error if the index is out of range.
+        fn try_get_node(&self, index: u32) -> Result<Node, MinimalError>;
         /// Get Node of a List as Element.
         fn get_el(&self, index: u32) -> Element;
+        /// Get Node of a List as Element, or an error if it is missing or not an Element.
+        fn try_get_el(&self, index: u32) -> Result<Element, MinimalError>;
         /// Get Node of a List as HtmlElement.
         fn get_html(&self, index: u32) -> HtmlElement;
+        /// Get Node of a List as HtmlElement, or an error if it is missing or not an HtmlElement.
+        fn try_get_html(&self, index: u32) -> Result<HtmlElement, MinimalError>;
+        /// Get Node of a List as any element type, like `HtmlInputElement`.
+        fn get_as<T: JsCast>(&self, index: u32) -> T;
+        /// Get Node of a List as any element type, or an error if it is missing or has another type.
+        fn try_get_as<T: JsCast>(&self, index: u32) -> Result<T, MinimalError>;
+        /// Iterate over the Nodes of a List as any element type, with an error for every other Node.
+        fn iter_as<T: JsCast>(&self) -> TypedNodes<T>;
+        /// Iterate over the Nodes of a List.
+        fn iter_nodes(&self) -> Nodes;
+        /// Iterate over the Nodes of a List as Element, with an error for every other Node.
+        fn iter_el(&self) -> TypedNodes<Element>;
+        /// Iterate over the Nodes of a List as HtmlElement, with an error for every other Node.
+        fn iter_html(&self) -> TypedNodes<HtmlElement>;
         /// Add a class to all Nodes of a List.
-        fn add_list_class<'a>(&self, value: &'a str);
+        fn add_list_class(&self, value: &str);
+        /// Add a class to all Nodes of a List, or an error on the first Node that is not an Element.
+        fn try_add_list_class(&self, value: &str) -> Result<(), MinimalError>;
         /// Remove a class to all Nodes of a List.
-        fn remove_list_class<'a>(&self, value: &'a str);
+        fn remove_list_class(&self, value: &str);
+        /// Remove a class to all Nodes of a List, or an error on the first Node that is not an Element.
+        fn try_remove_list_class(&self, value: &str) -> Result<(), MinimalError>;
+        /// Add or remove a class to all Nodes of a List, same as toggle_class_to().
+        fn toggle_list_class_to(&self, value: &str, force: bool);
+        /// Add or remove a class to all Nodes of a List, or an error on the first Node that is not an Element.
+        fn try_toggle_list_class_to(&self, value: &str, force: bool) -> Result<(), MinimalError>;
     }
 
     /// Some function for a Node element.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let document = Minimal::document();
     /// let h1 = document.query_selector_list("h1");
     /// let first_child_html = h1.get_node(0).to_html();
@@ -283,334 +732,1083 @@ pub mod utils {
     /// let first_child_html = h1.get_html(0);
     /// assert_eq!(<HtmlElement>, first_child_html);
     /// ```
-    ///
     pub trait MinimalNode {
         /// Convert a Node to Element.
         fn to_el(&self) -> Element;
+        /// Convert a Node to Element, or an error if it is not one.
+        fn try_to_el(&self) -> Result<Element, MinimalError>;
         /// Convert a Node to HtmlElement.
         fn to_html(&self) -> HtmlElement;
+        /// Convert a Node to HtmlElement, or an error if it is not one.
+        fn try_to_html(&self) -> Result<HtmlElement, MinimalError>;
+    }
+
+    /// Some function for everything that can be queried by selector:
+    /// Document, Element, ShadowRoot and DocumentFragment.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use Minimal::*;
+    ///
+    /// fn hide_all(root: &impl Queryable) {
+    ///     for el in root.query_selector_list(".spoiler") {
+    ///         el.to_html().set_prop("display", "none");
+    ///     }
+    /// }
+    ///
+    /// let document = Minimal::document();
+    /// hide_all(&document);
+    /// hide_all(&document.query_selector_el("article"));
+    /// ```
+    pub trait Queryable {
+        /// Get a List of all elements by selector.
+        fn query_selector_list(&self, value: &str) -> ElementList;
+        /// Get a List of all elements by selector, or an error if the selector is invalid.
+        fn try_query_selector_list(&self, value: &str) -> Result<ElementList, MinimalError>;
+        /// Get element by selector as Element.
+        fn query_selector_el(&self, value: &str) -> Element;
+        /// Get element by selector as Element, or an error if there is none.
+        fn try_query_selector_el(&self, value: &str) -> Result<Element, MinimalError>;
+        /// Get element by selector as HtmlElement.
+        fn query_selector_html(&self, value: &str) -> HtmlElement;
+        /// Get element by selector as HtmlElement, or an error if there is none.
+        fn try_query_selector_html(&self, value: &str) -> Result<HtmlElement, MinimalError>;
+        /// Get element by selector as Element, `None` if nothing matches.
+        fn find_el(&self, value: &str) -> Result<Option<Element>, MinimalError>;
+        /// Get element by selector as HtmlElement, `None` if nothing matches.
+        fn find_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError>;
+        /// Get element by selector as any element type, like `HtmlInputElement`.
+        fn query_as<T: JsCast>(&self, value: &str) -> T;
+        /// Get element by selector as any element type, or an error if there is none or it has another type.
+        fn try_query_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError>;
+        /// Get element by selector as any element type, `None` if nothing matches.
+        fn find_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError>;
+        /// Wait for an element to match selector, or a timeout error after ms
+        /// milliseconds, with the `async` feature.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use Minimal::*;
+        ///
+        /// spawn_local(async {
+        ///     let document = Minimal::document();
+        ///     match document.wait_for_selector(".result", 5000).await {
+        ///         Ok(result) => println!("{}", result.inner_html()),
+        ///         Err(e) => println!("{}", e),
+        ///     }
+        /// });
+        /// ```
+        #[cfg(feature = "async")]
+        fn wait_for_selector(&self, value: &str, ms: u32) -> WaitFor<Element>;
+        /// Wait for an element to match selector as any element type, or a timeout error.
+        #[cfg(feature = "async")]
+        fn wait_for_selector_as<T: JsCast + 'static>(&self, value: &str, ms: u32) -> WaitFor<T>;
+        /// Wait for no element to match selector, or a timeout error after ms milliseconds.
+        #[cfg(feature = "async")]
+        fn wait_for_removal(&self, value: &str, ms: u32) -> WaitFor<()>;
+    }
+
+    /// Change the url of document without reloading, keeping the history state.
+    fn replace_url(document: &Document, url: &str) -> Result<(), MinimalError> {
+        let history = document
+            .try_get_default_view()?
+            .history()
+            .map_err(|e| MinimalError::js(Target::Member("history"), e))?;
+        let state = history
+            .state()
+            .map_err(|e| MinimalError::js(Target::Member("state"), e))?;
+        history
+            .replace_state_with_url(&state, "", Some(url))
+            .map_err(|e| MinimalError::js(Target::Member("replace_state"), e))
+    }
+
+    /// Get the window of the document of element, which is not the global one inside
+    /// an iframe.
+    fn element_view(element: &Element) -> Result<Window, MinimalError> {
+        element
+            .owner_document()
+            .ok_or(MinimalError::NotFound(Target::Member("owner_document")))?
+            .try_get_default_view()
     }
 
     //IMPL TRAIT
 
     impl MinimalWindow for Window {
         fn document_page(&self) -> Document {
-            self.document().expect("no document found")
+            self.try_document_page().or_panic()
+        }
+        fn try_document_page(&self) -> Result<Document, MinimalError> {
+            self.document()
+                .ok_or(MinimalError::NotFound(Target::Member("document")))
         }
         fn document_element_el(&self) -> Element {
-            self.document_page()
-                .document_element()
-                .expect("no document element found")
+            self.try_document_element_el().or_panic()
+        }
+        fn try_document_element_el(&self) -> Result<Element, MinimalError> {
+            self.try_document_page()?.try_document_element_el()
         }
         fn document_element_html(&self) -> HtmlElement {
-            self.document_page()
-                .document_element()
-                .expect("no document element found")
-                .dyn_into::<HtmlElement>()
-                .expect("no document element to convert")
+            self.try_document_element_html().or_panic()
+        }
+        fn try_document_element_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.try_document_page()?.try_document_element_html()
         }
         fn get_name(&self) -> String {
-            self.name().expect("No name found")
+            self.try_get_name().or_panic()
+        }
+        fn try_get_name(&self) -> Result<String, MinimalError> {
+            self.name()
+                .map_err(|e| MinimalError::js(Target::Member("name"), e))
         }
         fn get_scroll_x(&self) -> f64 {
-            self.scroll_x().expect("No scroll x found")
+            self.try_get_scroll_x().or_panic()
+        }
+        fn try_get_scroll_x(&self) -> Result<f64, MinimalError> {
+            self.scroll_x()
+                .map_err(|e| MinimalError::js(Target::Member("scroll_x"), e))
         }
         fn get_scroll_y(&self) -> f64 {
-            self.scroll_y().expect("No scroll y found")
+            self.try_get_scroll_y().or_panic()
+        }
+        fn try_get_scroll_y(&self) -> Result<f64, MinimalError> {
+            self.scroll_y()
+                .map_err(|e| MinimalError::js(Target::Member("scroll_y"), e))
         }
         fn get_page_x_offset(&self) -> f64 {
-            self.page_x_offset().expect("No page x offset found")
+            self.try_get_page_x_offset().or_panic()
+        }
+        fn try_get_page_x_offset(&self) -> Result<f64, MinimalError> {
+            self.page_x_offset()
+                .map_err(|e| MinimalError::js(Target::Member("page_x_offset"), e))
         }
         fn get_page_y_offset(&self) -> f64 {
-            self.page_y_offset().expect("No page y offset found")
+            self.try_get_page_y_offset().or_panic()
+        }
+        fn try_get_page_y_offset(&self) -> Result<f64, MinimalError> {
+            self.page_y_offset()
+                .map_err(|e| MinimalError::js(Target::Member("page_y_offset"), e))
+        }
+        fn get_screen_x(&self) -> f64 {
+            self.try_get_screen_x().or_panic()
+        }
+        fn try_get_screen_x(&self) -> Result<f64, MinimalError> {
+            let value = self
+                .screen_x()
+                .map_err(|e| MinimalError::js(Target::Member("screen_x"), e))?;
+            number(value, || Target::Member("screen_x"))
+        }
+        fn get_screen_y(&self) -> f64 {
+            self.try_get_screen_y().or_panic()
+        }
+        fn try_get_screen_y(&self) -> Result<f64, MinimalError> {
+            let value = self
+                .screen_y()
+                .map_err(|e| MinimalError::js(Target::Member("screen_y"), e))?;
+            number(value, || Target::Member("screen_y"))
+        }
+        fn get_outer_height(&self) -> f64 {
+            self.try_get_outer_height().or_panic()
+        }
+        fn try_get_outer_height(&self) -> Result<f64, MinimalError> {
+            let value = self
+                .outer_height()
+                .map_err(|e| MinimalError::js(Target::Member("outer_height"), e))?;
+            number(value, || Target::Member("outer_height"))
+        }
+        fn get_outer_width(&self) -> f64 {
+            self.try_get_outer_width().or_panic()
+        }
+        fn try_get_outer_width(&self) -> Result<f64, MinimalError> {
+            let value = self
+                .outer_width()
+                .map_err(|e| MinimalError::js(Target::Member("outer_width"), e))?;
+            number(value, || Target::Member("outer_width"))
+        }
+        fn inner_size(&self) -> Size {
+            self.try_inner_size().or_panic()
+        }
+        fn try_inner_size(&self) -> Result<Size, MinimalError> {
+            let width = self
+                .inner_width()
+                .map_err(|e| MinimalError::js(Target::Member("inner_width"), e))?;
+            let height = self
+                .inner_height()
+                .map_err(|e| MinimalError::js(Target::Member("inner_height"), e))?;
+            Ok(Size::new(
+                number(width, || Target::Member("inner_width"))?,
+                number(height, || Target::Member("inner_height"))?,
+            ))
+        }
+        fn device_pixel_ratio(&self) -> f64 {
+            Window::device_pixel_ratio(self)
+        }
+        fn viewport(&self) -> Rect {
+            self.try_viewport().or_panic()
+        }
+        fn try_viewport(&self) -> Result<Rect, MinimalError> {
+            let viewport = self
+                .visual_viewport()
+                .ok_or(MinimalError::NotFound(Target::Member("visual_viewport")))?;
+            Ok(Rect::new(
+                viewport.offset_left(),
+                viewport.offset_top(),
+                viewport.width(),
+                viewport.height(),
+            ))
+        }
+        fn metrics(&self) -> WindowMetrics {
+            self.try_metrics().or_panic()
+        }
+        fn try_metrics(&self) -> Result<WindowMetrics, MinimalError> {
+            let inner = self.try_inner_size()?;
+            Ok(WindowMetrics {
+                inner,
+                outer: Size::new(self.try_get_outer_width()?, self.try_get_outer_height()?),
+                screen_x: self.try_get_screen_x()?,
+                screen_y: self.try_get_screen_y()?,
+                scroll_x: self.try_get_scroll_x()?,
+                scroll_y: self.try_get_scroll_y()?,
+                device_pixel_ratio: MinimalWindow::device_pixel_ratio(self),
+                // Without a visual viewport, the layout viewport is all that is visible.
+                viewport: self
+                    .try_viewport()
+                    .unwrap_or_else(|_| Rect::new(0.0, 0.0, inner.width, inner.height)),
+            })
+        }
+        fn set_timeout<F: FnOnce() + 'static>(&self, ms: u32, closure: F) -> Timeout {
+            self.try_set_timeout(ms, closure).or_panic()
+        }
+        fn try_set_timeout<F: FnOnce() + 'static>(&self, ms: u32, closure: F) -> Result<Timeout, MinimalError> {
+            Timeout::new(self, ms, closure)
+        }
+        fn set_interval<F: FnMut() + 'static>(&self, ms: u32, closure: F) -> Interval {
+            self.try_set_interval(ms, closure).or_panic()
+        }
+        fn try_set_interval<F: FnMut() + 'static>(&self, ms: u32, closure: F) -> Result<Interval, MinimalError> {
+            Interval::new(self, ms, closure)
+        }
+        fn request_frame<F: FnOnce(f64) + 'static>(&self, closure: F) -> AnimationFrame {
+            self.try_request_frame(closure).or_panic()
+        }
+        fn try_request_frame<F: FnOnce(f64) + 'static>(&self, closure: F) -> Result<AnimationFrame, MinimalError> {
+            AnimationFrame::new(self, closure)
+        }
+        fn frame_loop<F: FnMut(f64) + 'static>(&self, closure: F) -> FrameLoop {
+            self.try_frame_loop(closure).or_panic()
+        }
+        fn try_frame_loop<F: FnMut(f64) + 'static>(&self, closure: F) -> Result<FrameLoop, MinimalError> {
+            FrameLoop::new(self, closure)
         }
-        fn get_screen_x(&self) -> JsValue {
-            self.screen_x().expect("No screen x found")
+    }
+
+    impl MinimalDocument for Document {
+        fn get_element_by_id_el(&self, value: &str) -> Element {
+            self.try_get_element_by_id_el(value).or_panic()
         }
-        fn get_screen_y(&self) -> JsValue {
-            self.screen_y().expect("No screen y found")
+        fn try_get_element_by_id_el(&self, value: &str) -> Result<Element, MinimalError> {
+            self.find_element_by_id_el(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::id(value)))
         }
-        fn get_outer_height(&self) -> JsValue {
-            self.outer_height().expect("No outer height found")
+        fn get_element_by_id_html(&self, value: &str) -> HtmlElement {
+            self.try_get_element_by_id_html(value).or_panic()
         }
-        fn get_outer_width(&self) -> JsValue {
-            self.outer_width().expect("No outer width found")
+        fn try_get_element_by_id_html(&self, value: &str) -> Result<HtmlElement, MinimalError> {
+            self.find_element_by_id_html(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::id(value)))
         }
-    }
-
-    impl MinimalDocument for Document {
-        fn get_element_by_id_el<'a>(&self, value: &'a str) -> Element {
-            self.get_element_by_id(value)
-                .expect(&("no element with id: ".to_owned() + value))
-        }
-        fn get_element_by_id_html<'a>(&self, value: &'a str) -> HtmlElement {
-            self.get_element_by_id(value)
-                .expect(&("no element with id: ".to_owned() + value))
-                .dyn_into::<HtmlElement>()
-                .expect("It's not possible convert no element")
-        }
-        fn query_selector_html<'a>(&self, value: &'a str) -> HtmlElement {
-            let value = match self
-                .query_selector(value)
-                .expect(&("No element with this selector found: ".to_owned() + value))
-            {
-                Some(e) => Some(
-                    e.to_html(),
-                ),
-                None => None,
-            };
-            value.expect("There's no an element")
-        }
-        fn query_selector_el<'a>(&self, value: &'a str) -> Element {
-            let value = match self
-                .query_selector(value)
-                .expect(&("No element with this selector found: ".to_owned() + value))
-            {
-                Some(e) => Some(e),
-                None => None,
-            };
-            value.expect("no element found")
-        }
-        fn query_selector_list<'a>(&self, value: &'a str) -> NodeList {
-            self.query_selector_all(value).expect("no one element found")
+        fn find_element_by_id_el(&self, value: &str) -> Result<Option<Element>, MinimalError> {
+            Ok(self.get_element_by_id(value))
+        }
+        fn find_element_by_id_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError> {
+            self.find_by_id_as(value)
+        }
+        fn get_by_id_as<T: JsCast>(&self, value: &str) -> T {
+            self.try_get_by_id_as(value).or_panic()
+        }
+        fn try_get_by_id_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError> {
+            self.find_by_id_as(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::id(value)))
+        }
+        fn find_by_id_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError> {
+            cast_option(self.get_element_by_id(value), || Target::id(value))
         }
         fn document_element_el(&self) -> Element {
-            self.document_element().expect("no document element found")
+            self.try_document_element_el().or_panic()
         }
-        fn document_element_html(&self) -> HtmlElement {
+        fn try_document_element_el(&self) -> Result<Element, MinimalError> {
             self.document_element()
-                .expect("no document element found")
-                .to_html()
+                .ok_or(MinimalError::NotFound(Target::Member("document_element")))
+        }
+        fn document_element_html(&self) -> HtmlElement {
+            self.try_document_element_html().or_panic()
+        }
+        fn try_document_element_html(&self) -> Result<HtmlElement, MinimalError> {
+            cast(self.try_document_element_el()?, || Target::Member("document_element"))
         }
         fn get_url(&self) -> String {
-            self.url().expect("No url found")
+            self.try_get_url().or_panic()
+        }
+        fn try_get_url(&self) -> Result<String, MinimalError> {
+            self.url()
+                .map_err(|e| MinimalError::js(Target::Member("url"), e))
         }
         fn get_location(&self) -> Location {
-            self.location().expect("No location found")
+            self.try_get_location().or_panic()
+        }
+        fn try_get_location(&self) -> Result<Location, MinimalError> {
+            self.location()
+                .ok_or(MinimalError::NotFound(Target::Member("location")))
         }
         fn get_hash(&self) -> String {
-            self.get_location().hash().expect("No hash found")
+            self.try_get_hash().or_panic()
+        }
+        fn try_get_hash(&self) -> Result<String, MinimalError> {
+            self.try_get_location()?
+                .hash()
+                .map_err(|e| MinimalError::js(Target::Member("hash"), e))
         }
         fn get_host(&self) -> String {
-            self.get_location().host().expect("No host found")
+            self.try_get_host().or_panic()
+        }
+        fn try_get_host(&self) -> Result<String, MinimalError> {
+            self.try_get_location()?
+                .host()
+                .map_err(|e| MinimalError::js(Target::Member("host"), e))
         }
         fn get_hostname(&self) -> String {
-            self.get_location().hostname().expect("No hostname found")
+            self.try_get_hostname().or_panic()
+        }
+        fn try_get_hostname(&self) -> Result<String, MinimalError> {
+            self.try_get_location()?
+                .hostname()
+                .map_err(|e| MinimalError::js(Target::Member("hostname"), e))
         }
         fn get_href(&self) -> String {
-            self.get_location().href().expect("No href found")
+            self.try_get_href().or_panic()
+        }
+        fn try_get_href(&self) -> Result<String, MinimalError> {
+            self.try_get_location()?
+                .href()
+                .map_err(|e| MinimalError::js(Target::Member("href"), e))
+        }
+        fn query_params(&self) -> QueryParams {
+            self.try_query_params().or_panic()
+        }
+        fn try_query_params(&self) -> Result<QueryParams, MinimalError> {
+            let search = self
+                .try_get_location()?
+                .search()
+                .map_err(|e| MinimalError::js(Target::Member("search"), e))?;
+            Ok(QueryParams::from_query(&search))
+        }
+        #[cfg(feature = "serde")]
+        fn query<T: serde::de::DeserializeOwned>(&self) -> T {
+            self.try_query().or_panic()
+        }
+        #[cfg(feature = "serde")]
+        fn try_query<T: serde::de::DeserializeOwned>(&self) -> Result<T, MinimalError> {
+            self.try_query_params()?.to_struct()
+        }
+        fn set_query(&self, query: &QueryParams) {
+            self.try_set_query(query).or_panic()
+        }
+        fn try_set_query(&self, query: &QueryParams) -> Result<(), MinimalError> {
+            let location = self.try_get_location()?;
+            let path = location
+                .pathname()
+                .map_err(|e| MinimalError::js(Target::Member("pathname"), e))?;
+            let hash = location
+                .hash()
+                .map_err(|e| MinimalError::js(Target::Member("hash"), e))?;
+            let url = Url::new(&path).with_query(query.clone());
+            replace_url(self, &format!("{}{}", url, hash))
+        }
+        fn set_hash(&self, hash: &str) {
+            self.try_set_hash(hash).or_panic()
+        }
+        fn try_set_hash(&self, hash: &str) -> Result<(), MinimalError> {
+            let location = self.try_get_location()?;
+            let path = location
+                .pathname()
+                .map_err(|e| MinimalError::js(Target::Member("pathname"), e))?;
+            let search = location
+                .search()
+                .map_err(|e| MinimalError::js(Target::Member("search"), e))?;
+            let hash = hash.strip_prefix('#').unwrap_or(hash);
+            if hash.is_empty() {
+                replace_url(self, &format!("{}{}", path, search))
+            } else {
+                replace_url(self, &format!("{}{}#{}", path, search, hash))
+            }
+        }
+        fn assign(&self, url: &str) {
+            self.try_assign(url).or_panic()
+        }
+        fn try_assign(&self, url: &str) -> Result<(), MinimalError> {
+            self.try_get_location()?
+                .assign(url)
+                .map_err(|e| MinimalError::js(Target::Member("assign"), e))
+        }
+        fn replace(&self, url: &str) {
+            self.try_replace(url).or_panic()
+        }
+        fn try_replace(&self, url: &str) -> Result<(), MinimalError> {
+            self.try_get_location()?
+                .replace(url)
+                .map_err(|e| MinimalError::js(Target::Member("replace"), e))
+        }
+        fn reload(&self) {
+            self.try_reload().or_panic()
+        }
+        fn try_reload(&self) -> Result<(), MinimalError> {
+            self.try_get_location()?
+                .reload()
+                .map_err(|e| MinimalError::js(Target::Member("reload"), e))
+        }
+        fn cookies(&self) -> HashMap<String, String> {
+            self.try_cookies().or_panic()
+        }
+        fn try_cookies(&self) -> Result<HashMap<String, String>, MinimalError> {
+            let document: HtmlDocument = cast(self.clone(), || Target::Member("cookie"))?;
+            let header = document
+                .cookie()
+                .map_err(|e| MinimalError::js(Target::Member("cookie"), e))?;
+            Ok(parse_cookies(&header))
+        }
+        fn set_cookie(&self, cookie: &Cookie) {
+            self.try_set_cookie(cookie).or_panic()
+        }
+        fn try_set_cookie(&self, cookie: &Cookie) -> Result<(), MinimalError> {
+            cookie.check()?;
+            let document: HtmlDocument = cast(self.clone(), || Target::Member("cookie"))?;
+            document
+                .set_cookie(&cookie.to_string())
+                .map_err(|e| MinimalError::js(Target::Member("cookie"), e))
+        }
+        fn remove_cookie(&self, cookie: &Cookie) {
+            self.try_remove_cookie(cookie).or_panic()
+        }
+        fn try_remove_cookie(&self, cookie: &Cookie) -> Result<(), MinimalError> {
+            self.try_set_cookie(&cookie.removal())
         }
         fn set_new_body(&self, e: HtmlElement) {
             self.set_body(Some(&e))
         }
         fn get_default_view(&self) -> Window {
-            self.default_view().expect("There's not a window")
+            self.try_get_default_view().or_panic()
+        }
+        fn try_get_default_view(&self) -> Result<Window, MinimalError> {
+            self.default_view()
+                .ok_or(MinimalError::NotFound(Target::Member("default_view")))
         }
     }
 
     impl MinimalElement for Element {
         fn to_html(&self) -> HtmlElement {
-            self.clone()
-                .dyn_into::<HtmlElement>()
-                .expect("It's not possible get an HtmlElement of Null")
+            self.try_to_html().or_panic()
         }
-        fn has_class<'a>(&self, value: &'a str) -> bool {
-            if self.class_name().contains(&("".to_owned() + value)) {
-                true
-            } else {
-                false
-            }
+        fn try_to_html(&self) -> Result<HtmlElement, MinimalError> {
+            cast(self.clone(), || Target::Member("element"))
         }
-        fn toggle_class<'a>(&self, value: &'a str) {
-            if self.has_class(value) {
-                self.remove_class(value);
-            } else {
-                self.add_class(value);
-            }
+        fn has_class(&self, value: &str) -> bool {
+            self.class_list().contains(value)
+        }
+        fn add_class(&self, value: &str) {
+            self.try_add_class(value).or_panic()
+        }
+        fn try_add_class(&self, value: &str) -> Result<(), MinimalError> {
+            self.class_list()
+                .add_1(value)
+                .map_err(|e| MinimalError::js(Target::class(value), e))
+        }
+        fn add_classes(&self, values: &[&str]) {
+            self.try_add_classes(values).or_panic()
+        }
+        fn try_add_classes(&self, values: &[&str]) -> Result<(), MinimalError> {
+            self.class_list()
+                .add(&values.iter().map(|v| JsValue::from_str(v)).collect())
+                .map_err(|e| MinimalError::js(Target::class(&values.join(" ")), e))
         }
-        fn add_class<'a>(&self, value: &'a str) {
-            self.set_class_name(&(self.class_name() + &" ".to_owned() + value));
+        fn remove_class(&self, value: &str) {
+            self.try_remove_class(value).or_panic()
         }
-        fn remove_class<'a>(&self, value: &'a str) {
-            self.set_class_name(&(self.class_name().replace(&(" ".to_owned() + value), "")));
+        fn try_remove_class(&self, value: &str) -> Result<(), MinimalError> {
+            self.class_list()
+                .remove_1(value)
+                .map_err(|e| MinimalError::js(Target::class(value), e))
+        }
+        fn remove_classes(&self, values: &[&str]) {
+            self.try_remove_classes(values).or_panic()
+        }
+        fn try_remove_classes(&self, values: &[&str]) -> Result<(), MinimalError> {
+            self.class_list()
+                .remove(&values.iter().map(|v| JsValue::from_str(v)).collect())
+                .map_err(|e| MinimalError::js(Target::class(&values.join(" ")), e))
+        }
+        fn replace_class(&self, old: &str, new: &str) -> bool {
+            self.try_replace_class(old, new).or_panic()
+        }
+        fn try_replace_class(&self, old: &str, new: &str) -> Result<bool, MinimalError> {
+            self.class_list()
+                .replace(old, new)
+                .map_err(|e| MinimalError::js(Target::class(new), e))
+        }
+        fn toggle_class(&self, value: &str) {
+            self.try_toggle_class(value).or_panic()
+        }
+        fn try_toggle_class(&self, value: &str) -> Result<(), MinimalError> {
+            self.class_list()
+                .toggle(value)
+                .map(|_| ())
+                .map_err(|e| MinimalError::js(Target::class(value), e))
+        }
+        fn toggle_class_to(&self, value: &str, force: bool) {
+            self.try_toggle_class_to(value, force).or_panic()
+        }
+        fn try_toggle_class_to(&self, value: &str, force: bool) -> Result<(), MinimalError> {
+            self.class_list()
+                .toggle_with_force(value, force)
+                .map(|_| ())
+                .map_err(|e| MinimalError::js(Target::class(value), e))
+        }
+        fn classes(&self) -> Classes {
+            Classes::new(self.class_list())
         }
         fn parent_element_el(&self) -> Element {
-            self.parent_element().expect("No parent element found")
+            self.try_parent_element_el().or_panic()
         }
-        fn parent_element_html(&self) -> HtmlElement {
-            self.parent_element_el()
-                .to_html()
+        fn try_parent_element_el(&self) -> Result<Element, MinimalError> {
+            self.find_parent_element_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("parent_element")))
         }
-        fn query_selector_el<'a>(&self, value: &'a str) -> Element {
-            self.query_selector(value).expect("No element found").expect("there's been a problem")
+        fn find_parent_element_el(&self) -> Result<Option<Element>, MinimalError> {
+            Ok(self.parent_element())
         }
-        fn query_selector_html<'a>(&self, value: &'a str) -> HtmlElement {
-            self.query_selector_el(value).to_html()
+        fn parent_element_html(&self) -> HtmlElement {
+            self.try_parent_element_html().or_panic()
+        }
+        fn try_parent_element_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_parent_element_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("parent_element")))
         }
-        fn query_selector_list<'a>(&self, value: &'a str) -> NodeList {
-            self.query_selector_all(value).expect("No element found")
+        fn find_parent_element_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.parent_element(), || Target::Member("parent_element"))
         }
         fn first_child_el(&self) -> Element {
-            self.first_child().expect("No first child found").to_el()
+            self.try_first_child_el().or_panic()
+        }
+        fn try_first_child_el(&self) -> Result<Element, MinimalError> {
+            self.find_first_child_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("first_child")))
+        }
+        fn find_first_child_el(&self) -> Result<Option<Element>, MinimalError> {
+            cast_option(self.first_child(), || Target::Member("first_child"))
         }
         fn first_child_html(&self) -> HtmlElement {
-            self.first_child().expect("No first child found").to_html()
+            self.try_first_child_html().or_panic()
+        }
+        fn try_first_child_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_first_child_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("first_child")))
+        }
+        fn find_first_child_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.first_child(), || Target::Member("first_child"))
         }
         fn last_child_el(&self) -> Element {
-            self.last_child().expect("No last child found").to_el()
+            self.try_last_child_el().or_panic()
+        }
+        fn try_last_child_el(&self) -> Result<Element, MinimalError> {
+            self.find_last_child_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("last_child")))
+        }
+        fn find_last_child_el(&self) -> Result<Option<Element>, MinimalError> {
+            cast_option(self.last_child(), || Target::Member("last_child"))
         }
         fn last_child_html(&self) -> HtmlElement {
-            self.last_child().expect("No last child found").to_html()
+            self.try_last_child_html().or_panic()
+        }
+        fn try_last_child_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_last_child_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("last_child")))
+        }
+        fn find_last_child_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.last_child(), || Target::Member("last_child"))
         }
         fn prev_sibling_el(&self) -> Element {
-            self.previous_sibling().expect("No prev sibling found").to_el()
+            self.try_prev_sibling_el().or_panic()
+        }
+        fn try_prev_sibling_el(&self) -> Result<Element, MinimalError> {
+            self.find_prev_sibling_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("previous_sibling")))
+        }
+        fn find_prev_sibling_el(&self) -> Result<Option<Element>, MinimalError> {
+            cast_option(self.previous_sibling(), || Target::Member("previous_sibling"))
         }
         fn prev_sibling_html(&self) -> HtmlElement {
-            self.previous_sibling().expect("No prev element found").to_html()
+            self.try_prev_sibling_html().or_panic()
+        }
+        fn try_prev_sibling_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_prev_sibling_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("previous_sibling")))
+        }
+        fn find_prev_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.previous_sibling(), || Target::Member("previous_sibling"))
+        }
+        fn closest_el(&self, value: &str) -> Element {
+            self.try_closest_el(value).or_panic()
+        }
+        fn try_closest_el(&self, value: &str) -> Result<Element, MinimalError> {
+            self.find_closest_el(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+        }
+        fn find_closest_el(&self, value: &str) -> Result<Option<Element>, MinimalError> {
+            self.closest(value)
+                .map_err(|e| MinimalError::js(Target::selector(value), e))
         }
-        fn closest_el<'a>(&self, value: &'a str) -> Element {
-            self.closest(value).expect("No closest element found").expect("There's been a problem")
+        fn closest_html(&self, value: &str) -> HtmlElement {
+            self.try_closest_html(value).or_panic()
         }
-        fn closest_html<'a>(&self, value: &'a str) -> HtmlElement {
-            self.closest_el(value).to_html()
+        fn try_closest_html(&self, value: &str) -> Result<HtmlElement, MinimalError> {
+            self.find_closest_html(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+        }
+        fn find_closest_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError> {
+            self.find_closest_as(value)
+        }
+        fn closest_as<T: JsCast>(&self, value: &str) -> T {
+            self.try_closest_as(value).or_panic()
+        }
+        fn try_closest_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError> {
+            self.find_closest_as(value)?
+                .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+        }
+        fn find_closest_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError> {
+            cast_option(self.find_closest_el(value)?, || Target::selector(value))
         }
         fn get_namespace_uri(&self) -> String {
-            self.namespace_uri().expect("No namespace found")
+            self.try_get_namespace_uri().or_panic()
+        }
+        fn try_get_namespace_uri(&self) -> Result<String, MinimalError> {
+            self.namespace_uri()
+                .ok_or(MinimalError::NotFound(Target::Member("namespace_uri")))
         }
         fn get_assigned_slot(&self) -> HtmlSlotElement {
-            self.assigned_slot().expect("No assigned slot found")
+            self.try_get_assigned_slot().or_panic()
+        }
+        fn try_get_assigned_slot(&self) -> Result<HtmlSlotElement, MinimalError> {
+            self.assigned_slot()
+                .ok_or(MinimalError::NotFound(Target::Member("assigned_slot")))
         }
         fn first_element_child_el(&self) -> Element {
-            self.first_element_child().expect("No first child found")
+            self.try_first_element_child_el().or_panic()
+        }
+        fn try_first_element_child_el(&self) -> Result<Element, MinimalError> {
+            self.find_first_element_child_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("first_element_child")))
+        }
+        fn find_first_element_child_el(&self) -> Result<Option<Element>, MinimalError> {
+            Ok(self.first_element_child())
         }
         fn last_element_child_el(&self) -> Element {
-            self.last_element_child().expect("No last child found")
+            self.try_last_element_child_el().or_panic()
+        }
+        fn try_last_element_child_el(&self) -> Result<Element, MinimalError> {
+            self.find_last_element_child_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("last_element_child")))
+        }
+        fn find_last_element_child_el(&self) -> Result<Option<Element>, MinimalError> {
+            Ok(self.last_element_child())
         }
         fn next_element_sibling_el(&self) -> Element {
-            self.next_element_sibling().expect("No next sibling found")
+            self.try_next_element_sibling_el().or_panic()
+        }
+        fn try_next_element_sibling_el(&self) -> Result<Element, MinimalError> {
+            self.find_next_element_sibling_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("next_element_sibling")))
+        }
+        fn find_next_element_sibling_el(&self) -> Result<Option<Element>, MinimalError> {
+            Ok(self.next_element_sibling())
         }
         fn prev_element_sibling_el(&self) -> Element {
-            self.previous_element_sibling()
-                .expect("No prev sibling found")
+            self.try_prev_element_sibling_el().or_panic()
+        }
+        fn try_prev_element_sibling_el(&self) -> Result<Element, MinimalError> {
+            self.find_prev_element_sibling_el()?
+                .ok_or(MinimalError::NotFound(Target::Member("previous_element_sibling")))
+        }
+        fn find_prev_element_sibling_el(&self) -> Result<Option<Element>, MinimalError> {
+            Ok(self.previous_element_sibling())
         }
         fn first_element_child_html(&self) -> HtmlElement {
-            self.first_element_child()
-                .expect("No first child found")
-                .to_html()
+            self.try_first_element_child_html().or_panic()
+        }
+        fn try_first_element_child_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_first_element_child_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("first_element_child")))
+        }
+        fn find_first_element_child_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.first_element_child(), || Target::Member("first_element_child"))
         }
         fn last_element_child_html(&self) -> HtmlElement {
-            self.last_element_child()
-                .expect("No last child found")
-                .to_html()
+            self.try_last_element_child_html().or_panic()
+        }
+        fn try_last_element_child_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_last_element_child_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("last_element_child")))
+        }
+        fn find_last_element_child_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.last_element_child(), || Target::Member("last_element_child"))
         }
         fn next_element_sibling_html(&self) -> HtmlElement {
-            self.next_element_sibling()
-                .expect("No next sibling found")
-                .to_html()
+            self.try_next_element_sibling_html().or_panic()
+        }
+        fn try_next_element_sibling_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_next_element_sibling_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("next_element_sibling")))
+        }
+        fn find_next_element_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.next_element_sibling(), || Target::Member("next_element_sibling"))
         }
         fn prev_element_sibling_html(&self) -> HtmlElement {
-            self.previous_element_sibling()
-                .expect("No prev sibling found")
-                .to_html()
+            self.try_prev_element_sibling_html().or_panic()
+        }
+        fn try_prev_element_sibling_html(&self) -> Result<HtmlElement, MinimalError> {
+            self.find_prev_element_sibling_html()?
+                .ok_or(MinimalError::NotFound(Target::Member("previous_element_sibling")))
         }
-        fn get_attr_node<'a>(&self, value: &'a str) -> Attr {
-            self.get_attribute_node(value).expect("No attribute node found")
+        fn find_prev_element_sibling_html(&self) -> Result<Option<HtmlElement>, MinimalError> {
+            cast_option(self.previous_element_sibling(), || Target::Member("previous_element_sibling"))
         }
-        fn get_attr<'a>(&self, value: &'a str) -> String {
-            self.get_attribute(value).expect("No attribute found")
+        fn get_attr_node(&self, value: &str) -> Attr {
+            self.try_get_attr_node(value).or_panic()
         }
-        fn get_attr_ns<'a>(&self, namespace: &'a str, localname: &'a str) -> String {
-            self.get_attribute_ns(Some(namespace), localname).expect("There's been an error")
+        fn try_get_attr_node(&self, value: &str) -> Result<Attr, MinimalError> {
+            self.get_attribute_node(value)
+                .ok_or_else(|| MinimalError::NotFound(Target::attribute(value)))
         }
-        fn toggle_attr<'a>(&self, value: &'a str) -> bool {
+        fn get_attr(&self, value: &str) -> String {
+            self.try_get_attr(value).or_panic()
+        }
+        fn try_get_attr(&self, value: &str) -> Result<String, MinimalError> {
+            self.get_attribute(value)
+                .ok_or_else(|| MinimalError::NotFound(Target::attribute(value)))
+        }
+        fn get_attr_ns(&self, namespace: &str, localname: &str) -> String {
+            self.try_get_attr_ns(namespace, localname).or_panic()
+        }
+        fn try_get_attr_ns(&self, namespace: &str, localname: &str) -> Result<String, MinimalError> {
+            self.get_attribute_ns(Some(namespace), localname)
+                .ok_or_else(|| MinimalError::NotFound(Target::attribute(localname)))
+        }
+        fn toggle_attr(&self, value: &str) -> bool {
+            self.try_toggle_attr(value).or_panic()
+        }
+        fn try_toggle_attr(&self, value: &str) -> Result<bool, MinimalError> {
             self.toggle_attribute(value)
-                .expect("It's not possible toggle this attribute")
+                .map_err(|e| MinimalError::js(Target::attribute(value), e))
         }
         fn get_pref(&self) -> String {
-            self.prefix().expect("No prefix found")
+            self.try_get_pref().or_panic()
+        }
+        fn try_get_pref(&self) -> Result<String, MinimalError> {
+            self.prefix()
+                .ok_or(MinimalError::NotFound(Target::Member("prefix")))
+        }
+        fn insert_adj_el(&self, where_: &str, element: Element) -> Element {
+            self.try_insert_adj_el(where_, element).or_panic()
         }
-        fn insert_adj_el<'s, 'a>(&self, where_: &'s str, element: Element) -> Element {
+        fn try_insert_adj_el(&self, where_: &str, element: Element) -> Result<Element, MinimalError> {
             self.insert_adjacent_element(where_, &element)
-                .expect("It has not been possible insert element")
-                .expect("There's been a problem")
+                .map_err(|e| MinimalError::js(Target::Member("insert_adjacent_element"), e))?
+                .ok_or(MinimalError::NotFound(Target::Member("insert_adjacent_element")))
         }
-        fn remove_attr<'a>(&self, value: &'a str) {
-            self.remove_attribute(value).expect("It's no possible remove attribute")
+        fn remove_attr(&self, value: &str) {
+            self.try_remove_attr(value).or_panic()
         }
-        fn has_match<'a>(&self, value: &'a str) -> bool {
-            self.matches(value).expect("There's been a problem")
+        fn try_remove_attr(&self, value: &str) -> Result<(), MinimalError> {
+            self.remove_attribute(value)
+                .map_err(|e| MinimalError::js(Target::attribute(value), e))
+        }
+        fn has_match(&self, value: &str) -> bool {
+            self.try_has_match(value).or_panic()
+        }
+        fn try_has_match(&self, value: &str) -> Result<bool, MinimalError> {
+            self.matches(value)
+                .map_err(|e| MinimalError::js(Target::selector(value), e))
         }
         fn get_node_value(&self) -> String {
-            self.node_value().expect("No node value found")
+            self.try_get_node_value().or_panic()
+        }
+        fn try_get_node_value(&self) -> Result<String, MinimalError> {
+            self.node_value()
+                .ok_or(MinimalError::NotFound(Target::Member("node_value")))
         }
         fn get_text_content(&self) -> String {
-            self.text_content().expect("No text content found")
+            self.try_get_text_content().or_panic()
+        }
+        fn try_get_text_content(&self) -> Result<String, MinimalError> {
+            self.text_content()
+                .ok_or(MinimalError::NotFound(Target::Member("text_content")))
+        }
+        fn app_child(&self, node: impl Into<Node>) -> Node {
+            self.try_app_child(node).or_panic()
+        }
+        fn try_app_child(&self, node: impl Into<Node>) -> Result<Node, MinimalError> {
+            self.append_child(&node.into())
+                .map_err(|e| MinimalError::js(Target::Member("append_child"), e))
+        }
+        fn rect(&self) -> Rect {
+            self.get_bounding_client_rect().into()
+        }
+        fn page_offset(&self) -> (f64, f64) {
+            self.try_page_offset().or_panic()
+        }
+        fn try_page_offset(&self) -> Result<(f64, f64), MinimalError> {
+            let rect = self.rect();
+            let window = element_view(self)?;
+            Ok((rect.left() + window.try_get_scroll_x()?, rect.top() + window.try_get_scroll_y()?))
+        }
+        fn client_size(&self) -> Size {
+            Size::new(self.client_width() as f64, self.client_height() as f64)
+        }
+        fn scroll_size(&self) -> Size {
+            Size::new(self.scroll_width() as f64, self.scroll_height() as f64)
+        }
+        fn is_in_viewport(&self, threshold: f64) -> bool {
+            self.try_is_in_viewport(threshold).or_panic()
+        }
+        fn try_is_in_viewport(&self, threshold: f64) -> Result<bool, MinimalError> {
+            // The client size of the root element is the viewport without scrollbars.
+            let root = self
+                .owner_document()
+                .and_then(|document| document.document_element())
+                .ok_or(MinimalError::NotFound(Target::Member("document_element")))?;
+            let viewport = Rect::new(0.0, 0.0, root.client_width() as f64, root.client_height() as f64);
+            Ok(self.rect().is_visible_in(&viewport, threshold))
         }
-        fn app_child(&self, node: Node) -> Node {
-            self.append_child(&node).expect("Failed to append child")
+        fn intersection_with(&self, other: &Element) -> Option<Rect> {
+            self.rect().intersection(&other.rect())
         }
     }
 
     impl MinimalHtml for HtmlElement {
         fn to_el(&self) -> Element {
-            self.clone()
-                .dyn_into::<Element>()
-                .expect("It's not possible get an Element of Null")
+            self.try_to_el().or_panic()
+        }
+        fn try_to_el(&self) -> Result<Element, MinimalError> {
+            cast(self.clone(), || Target::Member("element"))
         }
         fn offset_parent_el(&self) -> Element {
-            self.offset_parent().expect("No offset parent found")
+            self.try_offset_parent_el().or_panic()
         }
-        fn offset_parent_html(&self) -> HtmlElement {
+        fn try_offset_parent_el(&self) -> Result<Element, MinimalError> {
             self.offset_parent()
-                .expect("No offset parent found")
-                .to_html()
+                .ok_or(MinimalError::NotFound(Target::Member("offset_parent")))
+        }
+        fn offset_parent_html(&self) -> HtmlElement {
+            self.try_offset_parent_html().or_panic()
+        }
+        fn try_offset_parent_html(&self) -> Result<HtmlElement, MinimalError> {
+            cast(self.try_offset_parent_el()?, || Target::Member("offset_parent"))
+        }
+        fn set_prop(&self, property: &str, value: impl fmt::Display) {
+            self.try_set_prop(property, value).or_panic()
+        }
+        fn try_set_prop(&self, property: &str, value: impl fmt::Display) -> Result<(), MinimalError> {
+            self.style()
+                .set_property(property, &value.to_string())
+                .map_err(|e| MinimalError::js(Target::style(property), e))
+        }
+        fn set_styles(&self, styles: &[(&str, &dyn fmt::Display)]) {
+            self.try_set_styles(styles).or_panic()
+        }
+        fn try_set_styles(&self, styles: &[(&str, &dyn fmt::Display)]) -> Result<(), MinimalError> {
+            styles
+                .iter()
+                .try_for_each(|(property, value)| self.try_set_prop(property, value))
+        }
+        fn get_prop(&self, property: &str) -> String {
+            self.try_get_prop(property).or_panic()
+        }
+        fn try_get_prop(&self, property: &str) -> Result<String, MinimalError> {
+            self.style()
+                .get_property_value(property)
+                .map_err(|e| MinimalError::js(Target::style(property), e))
+        }
+        fn remove_prop(&self, property: &str) -> String {
+            self.try_remove_prop(property).or_panic()
+        }
+        fn try_remove_prop(&self, property: &str) -> Result<String, MinimalError> {
+            self.style()
+                .remove_property(property)
+                .map_err(|e| MinimalError::js(Target::style(property), e))
+        }
+        fn computed_prop(&self, property: &str) -> String {
+            self.try_computed_prop(property).or_panic()
         }
-        fn set_prop<'a, 's>(&self, property: &'a str, value: &'s str) {
-            self.style().set_property(property, value).expect("There's been a problem")
+        fn try_computed_prop(&self, property: &str) -> Result<String, MinimalError> {
+            element_view(self)?
+                .get_computed_style(self)
+                .map_err(|e| MinimalError::js(Target::Member("computed_style"), e))?
+                .ok_or(MinimalError::NotFound(Target::Member("computed_style")))?
+                .get_property_value(property)
+                .map_err(|e| MinimalError::js(Target::style(property), e))
         }
-        fn get_prop<'a>(&self, property: &'a str) -> String {
-            self.style().get_property_value(property).expect("There's been a problem")
+        fn computed_as<T: FromStr>(&self, property: &str) -> T {
+            self.try_computed_as(property).or_panic()
+        }
+        fn try_computed_as<T: FromStr>(&self, property: &str) -> Result<T, MinimalError> {
+            parse(self.try_computed_prop(property)?, || Target::style(property))
         }
     }
 
     impl MinimalList for NodeList {
         fn get_node(&self, index: u32) -> Node {
-            self.item(index).expect(&("no element in position ".to_owned() + &index.to_string()))
+            self.try_get_node(index).or_panic()
         }
-        fn get_el(&self, index: u32) -> Element {
+        fn try_get_node(&self, index: u32) -> Result<Node, MinimalError> {
             self.item(index)
-                .expect(&("no element in position ".to_owned() + &index.to_string()))
-                .dyn_into::<Element>()
-                .expect("It's not possible get an Element of Null")
+                .ok_or(MinimalError::NotFound(Target::Index(index)))
+        }
+        fn get_el(&self, index: u32) -> Element {
+            self.try_get_el(index).or_panic()
+        }
+        fn try_get_el(&self, index: u32) -> Result<Element, MinimalError> {
+            self.try_get_as(index)
         }
         fn get_html(&self, index: u32) -> HtmlElement {
-            self.item(index)
-                .expect(&("no element in position ".to_owned() + &index.to_string()))
-                .dyn_into::<HtmlElement>()
-                .expect("It's not possible get an HtmlElement of Null")
-        }
-        fn add_list_class<'a>(&self, value: &'a str) {
-            for i in 0..self.length() {
-                if !self.get_html(i).has_class(value) {
-                    self.get_html(i).add_class(value);
-                }
+            self.try_get_html(index).or_panic()
+        }
+        fn try_get_html(&self, index: u32) -> Result<HtmlElement, MinimalError> {
+            self.try_get_as(index)
+        }
+        fn get_as<T: JsCast>(&self, index: u32) -> T {
+            self.try_get_as(index).or_panic()
+        }
+        fn try_get_as<T: JsCast>(&self, index: u32) -> Result<T, MinimalError> {
+            cast(self.try_get_node(index)?, || Target::Index(index))
+        }
+        fn iter_as<T: JsCast>(&self) -> TypedNodes<T> {
+            TypedNodes::new(self.clone())
+        }
+        fn iter_nodes(&self) -> Nodes {
+            Nodes::new(self.clone())
+        }
+        fn iter_el(&self) -> TypedNodes<Element> {
+            TypedNodes::new(self.clone())
+        }
+        fn iter_html(&self) -> TypedNodes<HtmlElement> {
+            TypedNodes::new(self.clone())
+        }
+        fn add_list_class(&self, value: &str) {
+            self.try_add_list_class(value).or_panic()
+        }
+        fn try_add_list_class(&self, value: &str) -> Result<(), MinimalError> {
+            for element in self.iter_el() {
+                element?.try_add_class(value)?;
             }
+            Ok(())
         }
-        fn remove_list_class<'a>(&self, value: &'a str) {
-            for i in 0..self.length() {
-                if self.get_html(i).has_class(value) {
-                    self.get_html(i).remove_class(value);
-                }
+        fn remove_list_class(&self, value: &str) {
+            self.try_remove_list_class(value).or_panic()
+        }
+        fn try_remove_list_class(&self, value: &str) -> Result<(), MinimalError> {
+            for element in self.iter_el() {
+                element?.try_remove_class(value)?;
+            }
+            Ok(())
+        }
+        fn toggle_list_class_to(&self, value: &str, force: bool) {
+            self.try_toggle_list_class_to(value, force).or_panic()
+        }
+        fn try_toggle_list_class_to(&self, value: &str, force: bool) -> Result<(), MinimalError> {
+            for element in self.iter_el() {
+                element?.try_toggle_class_to(value, force)?;
             }
+            Ok(())
         }
     }
 
     impl MinimalNode for Node{
         fn to_el(&self) -> Element {
-            self.clone().dyn_into::<Element>().expect("No Node element found")
+            self.try_to_el().or_panic()
+        }
+        fn try_to_el(&self) -> Result<Element, MinimalError> {
+            cast(self.clone(), || Target::Member("node"))
         }
         fn to_html(&self) -> HtmlElement {
-            self.clone().dyn_into::<HtmlElement>().expect("No Node element found")
+            self.try_to_html().or_panic()
+        }
+        fn try_to_html(&self) -> Result<HtmlElement, MinimalError> {
+            cast(self.clone(), || Target::Member("node"))
         }
     }
+
+    macro_rules! impl_queryable {
+        ($($t:ty),*) => {$(
+            impl Queryable for $t {
+                fn query_selector_list(&self, value: &str) -> ElementList {
+                    self.try_query_selector_list(value).or_panic()
+                }
+                fn try_query_selector_list(&self, value: &str) -> Result<ElementList, MinimalError> {
+                    self.query_selector_all(value)
+                        .map(ElementList::from_query)
+                        .map_err(|e| MinimalError::js(Target::selector(value), e))
+                }
+                fn query_selector_el(&self, value: &str) -> Element {
+                    self.try_query_selector_el(value).or_panic()
+                }
+                fn try_query_selector_el(&self, value: &str) -> Result<Element, MinimalError> {
+                    self.find_el(value)?
+                        .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+                }
+                fn query_selector_html(&self, value: &str) -> HtmlElement {
+                    self.try_query_selector_html(value).or_panic()
+                }
+                fn try_query_selector_html(&self, value: &str) -> Result<HtmlElement, MinimalError> {
+                    self.find_html(value)?
+                        .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+                }
+                fn find_el(&self, value: &str) -> Result<Option<Element>, MinimalError> {
+                    self.query_selector(value)
+                        .map_err(|e| MinimalError::js(Target::selector(value), e))
+                }
+                fn find_html(&self, value: &str) -> Result<Option<HtmlElement>, MinimalError> {
+                    self.find_as(value)
+                }
+                fn query_as<T: JsCast>(&self, value: &str) -> T {
+                    self.try_query_as(value).or_panic()
+                }
+                fn try_query_as<T: JsCast>(&self, value: &str) -> Result<T, MinimalError> {
+                    self.find_as(value)?
+                        .ok_or_else(|| MinimalError::NotFound(Target::selector(value)))
+                }
+                fn find_as<T: JsCast>(&self, value: &str) -> Result<Option<T>, MinimalError> {
+                    cast_option(self.find_el(value)?, || Target::selector(value))
+                }
+                #[cfg(feature = "async")]
+                fn wait_for_selector(&self, value: &str, ms: u32) -> WaitFor<Element> {
+                    self.wait_for_selector_as(value, ms)
+                }
+                #[cfg(feature = "async")]
+                fn wait_for_selector_as<T: JsCast + 'static>(&self, value: &str, ms: u32) -> WaitFor<T> {
+                    let root = self.clone();
+                    let selector = value.to_owned();
+                    WaitFor::new(self, Target::selector(value), ms, move || root.find_as(&selector))
+                }
+                #[cfg(feature = "async")]
+                fn wait_for_removal(&self, value: &str, ms: u32) -> WaitFor<()> {
+                    let root = self.clone();
+                    let selector = value.to_owned();
+                    WaitFor::new(self, Target::selector(value), ms, move || {
+                        root.find_el(&selector).map(|found| found.is_none().then_some(()))
+                    })
+                }
+            }
+        )*};
+    }
+
+    impl_queryable!(Document, Element, ShadowRoot, DocumentFragment);
 }
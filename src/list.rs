@@ -0,0 +1,285 @@
+//! Iterators over a NodeList and the classes of an Element.
+
+use crate::error::{cast, MinimalError, Target};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use wasm_bindgen::JsCast;
+use web_sys::{DomTokenList, Element, Node, NodeList};
+
+/// Iterator over the Nodes of a NodeList, returned by `iter_nodes()`.
+#[derive(Debug, Clone)]
+pub struct Nodes {
+    list: NodeList,
+    front: u32,
+    back: u32,
+}
+
+impl Nodes {
+    pub(crate) fn new(list: NodeList) -> Self {
+        let back = list.length();
+        Nodes {
+            list,
+            front: 0,
+            back,
+        }
+    }
+
+    fn next_indexed(&mut self) -> Option<(u32, Node)> {
+        if self.front >= self.back {
+            return None;
+        }
+        let index = self.front;
+        self.front += 1;
+        self.item(index)
+    }
+
+    fn next_back_indexed(&mut self) -> Option<(u32, Node)> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.item(self.back)
+    }
+
+    fn item(&mut self, index: u32) -> Option<(u32, Node)> {
+        match self.list.item(index) {
+            Some(node) => Some((index, node)),
+            // A live list shrank under us, stop here.
+            None => {
+                self.front = self.back;
+                None
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.back - self.front) as usize
+    }
+}
+
+impl Iterator for Nodes {
+    type Item = Node;
+
+    fn next(&mut self) -> Option<Node> {
+        self.next_indexed().map(|(_, node)| node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl DoubleEndedIterator for Nodes {
+    fn next_back(&mut self) -> Option<Node> {
+        self.next_back_indexed().map(|(_, node)| node)
+    }
+}
+
+impl ExactSizeIterator for Nodes {}
+
+impl FusedIterator for Nodes {}
+
+/// Iterator casting every Node of a NodeList, returned by `iter_el()` and `iter_html()`.
+///
+/// A Node of another type (a text node, an SVG element for `iter_html()`, ...) is
+/// reported as `MinimalError::WrongType` with its index. Use `.filter_map(Result::ok)`
+/// to skip them instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// let body = document.query_selector_el("body");
+/// for child in body.child_nodes().iter_html().filter_map(Result::ok) {
+///     child.set_prop("outline", "1px solid red");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedNodes<T> {
+    nodes: Nodes,
+    marker: PhantomData<T>,
+}
+
+impl<T> TypedNodes<T> {
+    pub(crate) fn new(list: NodeList) -> Self {
+        TypedNodes {
+            nodes: Nodes::new(list),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: JsCast> Iterator for TypedNodes<T> {
+    type Item = Result<T, MinimalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes
+            .next_indexed()
+            .map(|(index, node)| cast(node, || Target::Index(index)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T: JsCast> DoubleEndedIterator for TypedNodes<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nodes
+            .next_back_indexed()
+            .map(|(index, node)| cast(node, || Target::Index(index)))
+    }
+}
+
+impl<T: JsCast> ExactSizeIterator for TypedNodes<T> {}
+
+impl<T: JsCast> FusedIterator for TypedNodes<T> {}
+
+/// A NodeList returned by `query_selector_list`, where every Node is an Element.
+///
+/// It derefs to the NodeList, so every `MinimalList` method still works, and it can be
+/// iterated directly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// for p in document.query_selector_list("p") {
+///     p.add_class("read");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementList(NodeList);
+
+impl ElementList {
+    /// Wrap the result of `query_selector_all`, which only ever holds Elements.
+    pub(crate) fn from_query(list: NodeList) -> Self {
+        ElementList(list)
+    }
+
+    /// Get the wrapped NodeList.
+    pub fn into_inner(self) -> NodeList {
+        self.0
+    }
+}
+
+impl Deref for ElementList {
+    type Target = NodeList;
+
+    fn deref(&self) -> &NodeList {
+        &self.0
+    }
+}
+
+impl AsRef<NodeList> for ElementList {
+    fn as_ref(&self) -> &NodeList {
+        &self.0
+    }
+}
+
+impl From<ElementList> for NodeList {
+    fn from(list: ElementList) -> Self {
+        list.0
+    }
+}
+
+impl IntoIterator for ElementList {
+    type Item = Element;
+    type IntoIter = Elements;
+
+    fn into_iter(self) -> Elements {
+        Elements {
+            nodes: Nodes::new(self.0),
+        }
+    }
+}
+
+impl IntoIterator for &ElementList {
+    type Item = Element;
+    type IntoIter = Elements;
+
+    fn into_iter(self) -> Elements {
+        self.clone().into_iter()
+    }
+}
+
+/// Iterator over the Elements of an ElementList.
+#[derive(Debug, Clone)]
+pub struct Elements {
+    nodes: Nodes,
+}
+
+impl Iterator for Elements {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        self.nodes.next().map(JsCast::unchecked_into)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Elements {
+    fn next_back(&mut self) -> Option<Element> {
+        self.nodes.next_back().map(JsCast::unchecked_into)
+    }
+}
+
+impl ExactSizeIterator for Elements {}
+
+impl FusedIterator for Elements {}
+
+/// Iterator over the classes of an Element, returned by `classes()`.
+#[derive(Debug, Clone)]
+pub struct Classes {
+    list: DomTokenList,
+    front: u32,
+    back: u32,
+}
+
+impl Classes {
+    pub(crate) fn new(list: DomTokenList) -> Self {
+        let back = list.length();
+        Classes {
+            list,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl Iterator for Classes {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        self.list.item(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Classes {
+    fn next_back(&mut self) -> Option<String> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.list.item(self.back)
+    }
+}
+
+impl ExactSizeIterator for Classes {}
@@ -0,0 +1,466 @@
+//! Observers of the DOM, disconnected when their handle is dropped.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::geometry::{Rect, Size};
+use crate::utils::MinimalNode;
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit, MutationObserver,
+    MutationObserverInit, MutationRecord, Node, ResizeObserver, ResizeObserverEntry, ResizeObserverSize,
+};
+
+/// What `observe_mutations()` looks at, same as the options of `observe()`.
+///
+/// The old values of attributes and texts are always recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MutationOptions {
+    /// Children added and removed.
+    pub child_list: bool,
+    /// Attributes changed.
+    pub attributes: bool,
+    /// The whole subtree instead of the target only.
+    pub subtree: bool,
+    /// Only these attributes, all of them if empty.
+    pub attribute_filter: Vec<String>,
+    /// Texts changed.
+    pub character_data: bool,
+}
+
+impl MutationOptions {
+    fn to_init(&self) -> MutationObserverInit {
+        let init = MutationObserverInit::new();
+        init.set_child_list(self.child_list);
+        init.set_subtree(self.subtree);
+        init.set_character_data(self.character_data);
+        init.set_character_data_old_value(self.character_data);
+        // A filter turns attributes on by itself, as in the browser.
+        let attributes = self.attributes || !self.attribute_filter.is_empty();
+        init.set_attributes(attributes);
+        init.set_attribute_old_value(attributes);
+        if !self.attribute_filter.is_empty() {
+            let filter: Array = self.attribute_filter.iter().map(|name| JsValue::from_str(name)).collect();
+            init.set_attribute_filter(&filter);
+        }
+        init
+    }
+}
+
+/// A change seen by `observe_mutations()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    /// A node was added to the children of a node.
+    ChildAdded(Node),
+    /// A node was removed from the children of a node.
+    ChildRemoved(Node),
+    /// An attribute of element was set or removed.
+    AttributeChanged {
+        element: Element,
+        name: String,
+        /// The value before the change, `None` if the attribute was missing.
+        old: Option<String>,
+    },
+    /// The text of a text or comment node changed.
+    TextChanged {
+        node: Node,
+        /// The text before the change.
+        old: Option<String>,
+    },
+}
+
+impl Mutation {
+    /// Split a MutationRecord, one Mutation per added or removed node.
+    fn from_record(record: &MutationRecord) -> Vec<Mutation> {
+        match record.type_().as_str() {
+            "childList" => {
+                let added = record.added_nodes();
+                let removed = record.removed_nodes();
+                (0..added.length())
+                    .filter_map(|index| added.get(index).map(Mutation::ChildAdded))
+                    .chain((0..removed.length()).filter_map(|index| removed.get(index).map(Mutation::ChildRemoved)))
+                    .collect()
+            }
+            "attributes" => match record.target().map(|node| node.try_to_el()) {
+                Some(Ok(element)) => vec![Mutation::AttributeChanged {
+                    element,
+                    name: record.attribute_name().unwrap_or_default(),
+                    old: record.old_value(),
+                }],
+                _ => Vec::new(),
+            },
+            "characterData" => match record.target() {
+                Some(node) => vec![Mutation::TextChanged {
+                    node,
+                    old: record.old_value(),
+                }],
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A MutationObserver from `observe_mutations()`.
+///
+/// Dropping the handle disconnects the observer and frees the wasm Closure. Call
+/// `forget()` to keep it for the whole life of the page.
+#[must_use = "the observer is disconnected as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct MutationObserverHandle {
+    observer: MutationObserver,
+    closure: Option<Closure<dyn FnMut(Array)>>,
+}
+
+impl MutationObserverHandle {
+    /// Get the wrapped web_sys MutationObserver.
+    pub fn inner(&self) -> &MutationObserver {
+        &self.observer
+    }
+
+    /// Keep the observer forever, leaking the Closure.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+}
+
+impl Drop for MutationObserverHandle {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            self.observer.disconnect();
+        }
+    }
+}
+
+/// Observe the changes to target, calling closure once per change.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let widget = Minimal::document().query_selector_el("#widget");
+/// let options = MutationOptions { child_list: true, subtree: true, ..Default::default() };
+/// observe_mutations(&widget, options, |mutation| match mutation {
+///     Mutation::ChildAdded(node) => println!("added {}", node.node_name()),
+///     Mutation::ChildRemoved(node) => println!("removed {}", node.node_name()),
+///     _ => {}
+/// })
+/// .forget();
+/// ```
+pub fn observe_mutations<F>(target: &Node, options: MutationOptions, closure: F) -> MutationObserverHandle
+where
+    F: FnMut(Mutation) + 'static,
+{
+    try_observe_mutations(target, options, closure).or_panic()
+}
+
+/// Observe the changes to target, or an error if options observe nothing.
+pub fn try_observe_mutations<F>(
+    target: &Node,
+    options: MutationOptions,
+    mut closure: F,
+) -> Result<MutationObserverHandle, MinimalError>
+where
+    F: FnMut(Mutation) + 'static,
+{
+    let callback = Closure::<dyn FnMut(Array)>::new(move |records: Array| {
+        for record in records.iter() {
+            if let Ok(record) = record.dyn_into::<MutationRecord>() {
+                for mutation in Mutation::from_record(&record) {
+                    closure(mutation);
+                }
+            }
+        }
+    });
+    let observer = MutationObserver::new(callback.as_ref().unchecked_ref())
+        .map_err(|e| MinimalError::js(Target::Member("MutationObserver"), e))?;
+    observer
+        .observe_with_options(target, &options.to_init())
+        .map_err(|e| MinimalError::js(Target::Member("observe"), e))?;
+    Ok(MutationObserverHandle {
+        observer,
+        closure: Some(callback),
+    })
+}
+
+/// Where and when `observe_intersection()` reports, same as the options of
+/// IntersectionObserver.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntersectionOptions {
+    /// The scrolling ancestor to intersect with, the viewport if `None`.
+    pub root: Option<Element>,
+    /// Margins grown around the root, like `200px 0px` to load ahead of scrolling.
+    pub root_margin: String,
+    /// The visible ratios to report at, only 0 if empty.
+    pub thresholds: Vec<f64>,
+}
+
+impl IntersectionOptions {
+    fn to_init(&self) -> IntersectionObserverInit {
+        let init = IntersectionObserverInit::new();
+        init.set_root(self.root.as_ref());
+        if !self.root_margin.is_empty() {
+            init.set_root_margin(&self.root_margin);
+        }
+        if !self.thresholds.is_empty() {
+            let thresholds: Array = self.thresholds.iter().map(|&ratio| JsValue::from_f64(ratio)).collect();
+            init.set_threshold(&thresholds);
+        }
+        init
+    }
+}
+
+/// An element crossing a threshold, seen by `observe_intersection()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntersectionEntry {
+    pub target: Element,
+    /// If some of target is inside the root.
+    pub is_intersecting: bool,
+    /// The visible part of target, between 0 and 1.
+    pub ratio: f64,
+    /// The rect of target, as from `rect()`.
+    pub bounding_rect: Rect,
+    /// The visible part of target, empty when it is outside.
+    pub intersection_rect: Rect,
+    /// The rect of the root with its margins, `None` for a cross-origin viewport.
+    pub root_bounds: Option<Rect>,
+    /// When it happened, in milliseconds since the page loaded.
+    pub time: f64,
+}
+
+impl From<&IntersectionObserverEntry> for IntersectionEntry {
+    fn from(entry: &IntersectionObserverEntry) -> Self {
+        IntersectionEntry {
+            target: entry.target(),
+            is_intersecting: entry.is_intersecting(),
+            ratio: entry.intersection_ratio(),
+            bounding_rect: entry.bounding_client_rect().into(),
+            intersection_rect: entry.intersection_rect().into(),
+            root_bounds: entry.root_bounds().map(Rect::from),
+            time: entry.time(),
+        }
+    }
+}
+
+/// An IntersectionObserver from `observe_intersection()`.
+///
+/// Dropping the handle disconnects the observer and frees the wasm Closure. Call
+/// `forget()` to keep it for the whole life of the page.
+#[must_use = "the observer is disconnected as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct IntersectionObserverHandle {
+    observer: IntersectionObserver,
+    closure: Option<Closure<dyn FnMut(Array)>>,
+}
+
+impl IntersectionObserverHandle {
+    /// Observe one more element.
+    pub fn observe(&self, element: &Element) {
+        self.observer.observe(element);
+    }
+
+    /// Stop observing element, like an image already loaded.
+    pub fn unobserve(&self, element: &Element) {
+        self.observer.unobserve(element);
+    }
+
+    /// Get the wrapped web_sys IntersectionObserver.
+    pub fn inner(&self) -> &IntersectionObserver {
+        &self.observer
+    }
+
+    /// Keep the observer forever, leaking the Closure.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+}
+
+impl Drop for IntersectionObserverHandle {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            self.observer.disconnect();
+        }
+    }
+}
+
+/// Observe when elements enter and leave the root, calling closure with the entries
+/// that crossed a threshold. The first call has every element.
+///
+/// elements is anything yielding Elements, like the ElementList of
+/// `query_selector_list()`, or `[element]` for one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let document = Minimal::document();
+/// let options = IntersectionOptions { root_margin: "200px".to_owned(), ..Default::default() };
+/// observe_intersection(document.query_selector_list("img[data-src]"), options, |entries| {
+///     for entry in entries.iter().filter(|entry| entry.is_intersecting) {
+///         let src = entry.target.get_attribute("data-src").unwrap_or_default();
+///         entry.target.set_attribute("src", &src).ok();
+///     }
+/// })
+/// .forget();
+/// ```
+pub fn observe_intersection<I, F>(elements: I, options: IntersectionOptions, closure: F) -> IntersectionObserverHandle
+where
+    I: IntoIterator<Item = Element>,
+    F: FnMut(Vec<IntersectionEntry>) + 'static,
+{
+    try_observe_intersection(elements, options, closure).or_panic()
+}
+
+/// Observe when elements enter and leave the root, or an error if the margin or the
+/// thresholds are invalid.
+pub fn try_observe_intersection<I, F>(
+    elements: I,
+    options: IntersectionOptions,
+    mut closure: F,
+) -> Result<IntersectionObserverHandle, MinimalError>
+where
+    I: IntoIterator<Item = Element>,
+    F: FnMut(Vec<IntersectionEntry>) + 'static,
+{
+    let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+        let entries = entries
+            .iter()
+            .filter_map(|entry| entry.dyn_into::<IntersectionObserverEntry>().ok())
+            .map(|entry| IntersectionEntry::from(&entry))
+            .collect();
+        closure(entries);
+    });
+    let observer = IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options.to_init())
+        .map_err(|e| MinimalError::js(Target::Member("IntersectionObserver"), e))?;
+    for element in elements {
+        observer.observe(&element);
+    }
+    Ok(IntersectionObserverHandle {
+        observer,
+        closure: Some(callback),
+    })
+}
+
+/// A new size of an element, seen by `observe_resize()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResizeEntry {
+    pub target: Element,
+    /// The content box, inside the padding, relative to the padding box.
+    pub content_rect: Rect,
+    /// The border box, with padding and borders.
+    pub border_box: Size,
+}
+
+impl From<&ResizeObserverEntry> for ResizeEntry {
+    fn from(entry: &ResizeObserverEntry) -> Self {
+        let content_rect = Rect::from(entry.content_rect());
+        // Sizes are given along the writing mode, the inline one is the width in
+        // horizontal text. borderBoxSize is missing before Safari 15.4, and a single
+        // size rather than a list in old Firefox.
+        let key = JsValue::from_str("borderBoxSize");
+        let border_box = Some(entry)
+            .filter(|entry| Reflect::has(entry, &key).unwrap_or(false))
+            .and_then(|entry| Reflect::get(entry, &key).ok())
+            .and_then(|sizes| match sizes.dyn_into::<Array>() {
+                Ok(sizes) => sizes.get(0).dyn_into::<ResizeObserverSize>().ok(),
+                Err(size) => size.dyn_into::<ResizeObserverSize>().ok(),
+            })
+            .map(|size| Size::new(size.inline_size(), size.block_size()))
+            .unwrap_or_else(|| content_rect.size());
+        ResizeEntry {
+            target: entry.target(),
+            content_rect,
+            border_box,
+        }
+    }
+}
+
+/// A ResizeObserver from `observe_resize()`.
+///
+/// Dropping the handle disconnects the observer and frees the wasm Closure. Call
+/// `forget()` to keep it for the whole life of the page.
+#[must_use = "the observer is disconnected as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct ResizeObserverHandle {
+    observer: ResizeObserver,
+    closure: Option<Closure<dyn FnMut(Array)>>,
+}
+
+impl ResizeObserverHandle {
+    /// Observe one more element.
+    pub fn observe(&self, element: &Element) {
+        self.observer.observe(element);
+    }
+
+    /// Stop observing element.
+    pub fn unobserve(&self, element: &Element) {
+        self.observer.unobserve(element);
+    }
+
+    /// Get the wrapped web_sys ResizeObserver.
+    pub fn inner(&self) -> &ResizeObserver {
+        &self.observer
+    }
+
+    /// Keep the observer forever, leaking the Closure.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+}
+
+impl Drop for ResizeObserverHandle {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            self.observer.disconnect();
+        }
+    }
+}
+
+/// Observe the size of element, calling closure once when it starts and on every
+/// resize.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let card = Minimal::document().query_selector_el(".card");
+/// observe_resize(&card, |ResizeEntry { target, content_rect, .. }| {
+///     target.toggle_class_to("compact", content_rect.width() < 400.0);
+/// })
+/// .forget();
+/// ```
+pub fn observe_resize<F>(element: &Element, closure: F) -> ResizeObserverHandle
+where
+    F: FnMut(ResizeEntry) + 'static,
+{
+    try_observe_resize(element, closure).or_panic()
+}
+
+/// Observe the size of element, or the JavaScript exception.
+pub fn try_observe_resize<F>(element: &Element, mut closure: F) -> Result<ResizeObserverHandle, MinimalError>
+where
+    F: FnMut(ResizeEntry) + 'static,
+{
+    let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+        for entry in entries.iter() {
+            if let Ok(entry) = entry.dyn_into::<ResizeObserverEntry>() {
+                closure(ResizeEntry::from(&entry));
+            }
+        }
+    });
+    let observer = ResizeObserver::new(callback.as_ref().unchecked_ref())
+        .map_err(|e| MinimalError::js(Target::Member("ResizeObserver"), e))?;
+    observer.observe(element);
+    Ok(ResizeObserverHandle {
+        observer,
+        closure: Some(callback),
+    })
+}
@@ -0,0 +1,233 @@
+//! Route patterns like `/users/:id/*rest`, matched in Rust without a browser.
+
+use crate::error::{parse, MinimalError, OrPanic, Target};
+use crate::url::percent_decode;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    /// `*name` takes every segment left, `*` alone does not keep them.
+    Rest(Option<String>),
+}
+
+/// A route pattern: static segments, `:name` for one segment and `*name` for the rest
+/// of the path.
+///
+/// Empty segments are ignored, so `/users/` matches `/users`. The query and the hash
+/// of the path are ignored too.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::Route;
+///
+/// let route = Route::new("/users/:id/*rest");
+/// let params = route.matches("/users/42/posts/7?tab=1").unwrap();
+/// assert_eq!(params.get("id"), Some("42"));
+/// assert_eq!(params.get_as::<u32>("id"), 42);
+/// assert_eq!(params.get("rest"), Some("posts/7"));
+/// assert!(route.matches("/teams/42").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl Route {
+    /// Parse a route pattern. A segment after `*rest` is never reached.
+    pub fn new(pattern: &str) -> Self {
+        let segments = split(pattern)
+            .map(|segment| {
+                if let Some(name) = segment.strip_prefix(':') {
+                    Segment::Param(name.to_owned())
+                } else if let Some(name) = segment.strip_prefix('*') {
+                    Segment::Rest(Some(name.to_owned()).filter(|name| !name.is_empty()))
+                } else {
+                    Segment::Static(segment.to_owned())
+                }
+            })
+            .collect();
+        Route {
+            pattern: pattern.to_owned(),
+            segments,
+        }
+    }
+
+    /// Get the pattern of the route.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Match a path, getting its params, or `None` if it does not match.
+    pub fn matches(&self, path: &str) -> Option<Params> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let parts: Vec<&str> = split(path).collect();
+        let mut params = Params::default();
+        for (index, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Static(expected) => {
+                    if percent_decode(parts.get(index)?) != *expected {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    let part = parts.get(index)?;
+                    params.pairs.push((name.clone(), percent_decode(part)));
+                }
+                Segment::Rest(name) => {
+                    if let Some(name) = name {
+                        let rest = parts.get(index..).unwrap_or_default();
+                        let rest: Vec<String> = rest.iter().map(|part| percent_decode(part)).collect();
+                        params.pairs.push((name.clone(), rest.join("/")));
+                    }
+                    return Some(params);
+                }
+            }
+        }
+        if parts.len() == self.segments.len() {
+            Some(params)
+        } else {
+            None
+        }
+    }
+}
+
+fn split(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// The params of a matched Route, decoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    pairs: Vec<(String, String)>,
+}
+
+impl Params {
+    /// Get the value of a param.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get a param parsed as any FromStr type, like `u32`.
+    pub fn get_as<T: FromStr>(&self, name: &str) -> T {
+        self.try_get_as(name).or_panic()
+    }
+
+    /// Get a param parsed, or an error if it is missing or cannot be parsed.
+    pub fn try_get_as<T: FromStr>(&self, name: &str) -> Result<T, MinimalError> {
+        let value = self
+            .get(name)
+            .ok_or_else(|| MinimalError::NotFound(Target::param(name)))?;
+        parse(value.to_owned(), || Target::param(name))
+    }
+
+    /// Iterate over the names and values of the params, in the order of the pattern.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Check if there is no param.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Route;
+    use crate::error::{MinimalError, Target};
+
+    #[test]
+    fn static_routes() {
+        let route = Route::new("/about/team");
+        assert!(route.matches("/about/team").unwrap().is_empty());
+        assert!(route.matches("/about").is_none());
+        assert!(route.matches("/about/team/lead").is_none());
+        assert!(route.matches("/about/people").is_none());
+        assert!(Route::new("/").matches("/").is_some());
+        assert!(Route::new("/").matches("/home").is_none());
+        assert!(Route::new("").matches("/").is_some());
+    }
+
+    #[test]
+    fn params_capture_one_segment() {
+        let route = Route::new("/users/:id/posts/:post");
+        let params = route.matches("/users/42/posts/first-post").unwrap();
+        assert_eq!(params.get("id"), Some("42"));
+        assert_eq!(params.get("post"), Some("first-post"));
+        assert_eq!(params.get("other"), None);
+        let names: Vec<(&str, &str)> = params.iter().collect();
+        assert_eq!(names, vec![("id", "42"), ("post", "first-post")]);
+        assert!(route.matches("/users/42/posts").is_none());
+        assert!(route.matches("/users/42/posts/1/edit").is_none());
+        assert!(route.matches("/users/42/comments/1").is_none());
+    }
+
+    #[test]
+    fn rest_takes_the_remaining_segments() {
+        let route = Route::new("/files/*path");
+        assert_eq!(route.matches("/files/a/b/c.txt").unwrap().get("path"), Some("a/b/c.txt"));
+        assert_eq!(route.matches("/files/a").unwrap().get("path"), Some("a"));
+        assert_eq!(route.matches("/files").unwrap().get("path"), Some(""));
+        assert!(route.matches("/other/a").is_none());
+        let anonymous = Route::new("/docs/*");
+        assert!(anonymous.matches("/docs/a/b").unwrap().is_empty());
+        assert!(Route::new("*").matches("/anything/at/all").is_some());
+    }
+
+    #[test]
+    fn trailing_and_repeated_slashes_are_ignored() {
+        let route = Route::new("/users/:id/");
+        assert_eq!(route.matches("/users/7").unwrap().get("id"), Some("7"));
+        assert_eq!(route.matches("/users/7/").unwrap().get("id"), Some("7"));
+        assert_eq!(route.matches("users//7").unwrap().get("id"), Some("7"));
+    }
+
+    #[test]
+    fn query_and_hash_are_ignored() {
+        let route = Route::new("/search/:term");
+        assert_eq!(route.matches("/search/cats?page=2#top").unwrap().get("term"), Some("cats"));
+        assert_eq!(route.matches("/search/cats#a/b").unwrap().get("term"), Some("cats"));
+    }
+
+    #[test]
+    fn segments_are_percent_decoded() {
+        let route = Route::new("/tags/:tag/*rest");
+        let params = route.matches("/tags/caf%C3%A9%20bar/a%2Fb/c%20d").unwrap();
+        assert_eq!(params.get("tag"), Some("café bar"));
+        assert_eq!(params.get("rest"), Some("a/b/c d"));
+        assert!(Route::new("/hello world").matches("/hello%20world").is_some());
+    }
+
+    #[test]
+    fn get_as_parses_params() {
+        let params = Route::new("/items/:id/:name").matches("/items/12/abc").unwrap();
+        assert_eq!(params.get_as::<u32>("id"), 12);
+        assert_eq!(params.try_get_as::<String>("name"), Ok("abc".to_owned()));
+        assert_eq!(
+            params.try_get_as::<u32>("name"),
+            Err(MinimalError::Parse {
+                target: Target::Param("name".to_owned()),
+                expected: "u32",
+                found: "abc".to_owned(),
+            })
+        );
+        assert_eq!(
+            params.try_get_as::<u32>("missing"),
+            Err(MinimalError::NotFound(Target::Param("missing".to_owned())))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_as_panics_on_parse_failure() {
+        let params = Route::new("/items/:id").matches("/items/-1").unwrap();
+        let _: u32 = params.get_as("id");
+    }
+}
@@ -0,0 +1,349 @@
+//! Client-side router on the History API or on the hash of the url.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::event::{EventListenerHandle, MinimalEvents};
+use crate::route::{Params, Route};
+use crate::utils::{document, window, MinimalDocument, MinimalElement};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, Event, MouseEvent, Node};
+
+/// Where a Router keeps the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouterMode {
+    /// In the path of the url, like `/users/42`, with pushState().
+    History,
+    /// In the hash of the url, like `/#/users/42`, for servers serving one file.
+    Hash,
+}
+
+/// A navigation about to happen, handed to the guards of a Router.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Navigation {
+    /// The path of the current route.
+    pub from: String,
+    /// The path asked for.
+    pub to: String,
+}
+
+type Handler = Rc<dyn Fn(&Params)>;
+type NotFound = Rc<dyn Fn(&str)>;
+type Guard = Rc<dyn Fn(&Navigation) -> bool>;
+
+struct Inner {
+    mode: RouterMode,
+    routes: RefCell<Vec<(Route, Handler)>>,
+    not_found: RefCell<Option<NotFound>>,
+    guards: RefCell<Vec<Guard>>,
+    current: RefCell<Option<String>>,
+    listeners: RefCell<Vec<EventListenerHandle>>,
+}
+
+/// A client-side router, calling the handler of the first route matching the path.
+///
+/// After `start()`, it follows `popstate` and `hashchange`, and turns the clicks on
+/// same-origin links into navigations without reload. A click with a modifier key,
+/// on a link with `target` or `download`, or on a `#hash` link is left to the browser.
+///
+/// The Router is a handle: clones share the same routes, and the listeners are removed
+/// when the last clone is dropped, unless `forget()` keeps it for the whole page.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let router = Router::new(RouterMode::History)
+///     .route("/", |_| println!("home"))
+///     .route("/users/:id", |params| {
+///         let id: u32 = params.get_as("id");
+///         println!("user {}", id);
+///     })
+///     .route("/docs/*path", |params| println!("doc {:?}", params.get("path")))
+///     .not_found(|path| println!("no page at {}", path))
+///     .guard(|navigation| !navigation.from.starts_with("/editor"));
+/// router.start();
+/// router.navigate("/users/42");
+/// router.forget();
+/// ```
+#[derive(Clone)]
+pub struct Router {
+    inner: Rc<Inner>,
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let routes = self.inner.routes.borrow();
+        f.debug_struct("Router")
+            .field("mode", &self.inner.mode)
+            .field("routes", &routes.iter().map(|(route, _)| route.pattern()).collect::<Vec<_>>())
+            .field("current", &self.inner.current.borrow())
+            .finish()
+    }
+}
+
+impl Router {
+    /// A Router without routes, keeping the path as mode says.
+    pub fn new(mode: RouterMode) -> Self {
+        Router {
+            inner: Rc::new(Inner {
+                mode,
+                routes: RefCell::new(Vec::new()),
+                not_found: RefCell::new(None),
+                guards: RefCell::new(Vec::new()),
+                current: RefCell::new(None),
+                listeners: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Add a route like `/users/:id/*rest`, tried in the order they are added.
+    pub fn route<F: Fn(&Params) + 'static>(self, pattern: &str, handler: F) -> Self {
+        self.inner
+            .routes
+            .borrow_mut()
+            .push((Route::new(pattern), Rc::new(handler)));
+        self
+    }
+
+    /// Set the handler of the paths matching no route.
+    pub fn not_found<F: Fn(&str) + 'static>(self, handler: F) -> Self {
+        *self.inner.not_found.borrow_mut() = Some(Rc::new(handler));
+        self
+    }
+
+    /// Add a guard, returning false to cancel the navigation.
+    pub fn guard<F: Fn(&Navigation) -> bool + 'static>(self, guard: F) -> Self {
+        self.inner.guards.borrow_mut().push(Rc::new(guard));
+        self
+    }
+
+    /// Get the mode of the Router.
+    pub fn mode(&self) -> RouterMode {
+        self.inner.mode
+    }
+
+    /// Listen to the history and to the links, and run the route of the current path.
+    pub fn start(&self) {
+        self.try_start().or_panic()
+    }
+
+    /// Listen to the history and to the links, or get the JavaScript exception.
+    pub fn try_start(&self) -> Result<(), MinimalError> {
+        let window = window();
+        let mut listeners = Vec::new();
+        let weak = Rc::downgrade(&self.inner);
+        listeners.push(window.try_on("popstate", move |_| with_router(&weak, Router::follow))?);
+        if self.inner.mode == RouterMode::Hash {
+            let weak = Rc::downgrade(&self.inner);
+            listeners.push(window.try_on("hashchange", move |_| with_router(&weak, Router::follow))?);
+        }
+        let weak = Rc::downgrade(&self.inner);
+        listeners.push(document().try_on("click", move |event| {
+            with_router(&weak, |router| router.intercept(event))
+        })?);
+        *self.inner.listeners.borrow_mut() = listeners;
+        let path = self.try_current_path()?;
+        self.dispatch(&path);
+        Ok(())
+    }
+
+    /// Stop listening to the history and to the links.
+    pub fn stop(&self) {
+        self.inner.listeners.borrow_mut().clear();
+    }
+
+    /// Keep the Router listening for the whole life of the page.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+
+    /// Get the path in the url, with its query.
+    pub fn current_path(&self) -> String {
+        self.try_current_path().or_panic()
+    }
+
+    /// Get the path in the url, or an error if there is no location.
+    pub fn try_current_path(&self) -> Result<String, MinimalError> {
+        let document = document();
+        match self.inner.mode {
+            RouterMode::History => {
+                let location = document.try_get_location()?;
+                let path = location
+                    .pathname()
+                    .map_err(|e| MinimalError::js(Target::Member("pathname"), e))?;
+                let search = location
+                    .search()
+                    .map_err(|e| MinimalError::js(Target::Member("search"), e))?;
+                Ok(path + &search)
+            }
+            RouterMode::Hash => {
+                let hash = document.try_get_hash()?;
+                let path = hash.strip_prefix('#').unwrap_or(&hash);
+                Ok(if path.is_empty() { "/".to_owned() } else { path.to_owned() })
+            }
+        }
+    }
+
+    /// Go to path adding it to the history, or get false if a guard cancelled it.
+    pub fn navigate(&self, path: &str) -> bool {
+        self.try_navigate(path).or_panic()
+    }
+
+    /// Go to path adding it to the history, or get the JavaScript exception.
+    pub fn try_navigate(&self, path: &str) -> Result<bool, MinimalError> {
+        self.go(path, false)
+    }
+
+    /// Go to path in place of the current entry of the history, or get false if a
+    /// guard cancelled it.
+    pub fn redirect(&self, path: &str) -> bool {
+        self.try_redirect(path).or_panic()
+    }
+
+    /// Go to path in place of the current entry, or get the JavaScript exception.
+    pub fn try_redirect(&self, path: &str) -> Result<bool, MinimalError> {
+        self.go(path, true)
+    }
+
+    fn go(&self, path: &str, replace: bool) -> Result<bool, MinimalError> {
+        let from = self.inner.current.borrow().clone().unwrap_or_default();
+        if !self.allows(&from, path) {
+            return Ok(false);
+        }
+        self.write_url(path, replace)?;
+        self.dispatch(path);
+        Ok(true)
+    }
+
+    fn write_url(&self, path: &str, replace: bool) -> Result<(), MinimalError> {
+        let url = match self.inner.mode {
+            RouterMode::History => path.to_owned(),
+            RouterMode::Hash => format!("#{}", path),
+        };
+        let history = window()
+            .history()
+            .map_err(|e| MinimalError::js(Target::Member("history"), e))?;
+        if replace {
+            history
+                .replace_state_with_url(&JsValue::NULL, "", Some(&url))
+                .map_err(|e| MinimalError::js(Target::Member("replace_state"), e))
+        } else {
+            history
+                .push_state_with_url(&JsValue::NULL, "", Some(&url))
+                .map_err(|e| MinimalError::js(Target::Member("push_state"), e))
+        }
+    }
+
+    fn allows(&self, from: &str, to: &str) -> bool {
+        let navigation = Navigation {
+            from: from.to_owned(),
+            to: to.to_owned(),
+        };
+        // Clone the guards out, so a guard can add another one.
+        let guards = self.inner.guards.borrow().clone();
+        guards.iter().all(|guard| guard(&navigation))
+    }
+
+    fn dispatch(&self, path: &str) {
+        *self.inner.current.borrow_mut() = Some(path.to_owned());
+        // Find the handler first, so it can navigate again or add routes.
+        let matched = self
+            .inner
+            .routes
+            .borrow()
+            .iter()
+            .find_map(|(route, handler)| route.matches(path).map(|params| (handler.clone(), params)));
+        match matched {
+            Some((handler, params)) => handler(&params),
+            None => {
+                let not_found = self.inner.not_found.borrow().clone();
+                if let Some(not_found) = not_found {
+                    not_found(path);
+                }
+            }
+        }
+    }
+
+    /// Follow the url after the back and forward buttons or a hash change.
+    fn follow(&self) {
+        let to = match self.try_current_path() {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+        let from = self.inner.current.borrow().clone().unwrap_or_default();
+        if to == from {
+            return;
+        }
+        if self.allows(&from, &to) {
+            self.dispatch(&to);
+        } else {
+            // The url already changed, put back the one of the current route.
+            let _ = self.write_url(&from, true);
+        }
+    }
+
+    /// Navigate on a click on a same-origin link, instead of reloading.
+    fn intercept(&self, event: Event) {
+        let mouse = match event.dyn_ref::<MouseEvent>() {
+            Some(mouse) => mouse,
+            None => return,
+        };
+        if event.default_prevented()
+            || mouse.button() != 0
+            || mouse.ctrl_key()
+            || mouse.meta_key()
+            || mouse.shift_key()
+            || mouse.alt_key()
+        {
+            return;
+        }
+        let anchor = match link_of(&event) {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        let target = anchor.get_attribute("target").unwrap_or_default();
+        let href = anchor.get_attribute("href").unwrap_or_default();
+        if anchor.has_attribute("download") || !(target.is_empty() || target == "_self") || href.starts_with('#') {
+            return;
+        }
+        let same_origin = match (property(&anchor, "origin"), window().location().origin()) {
+            (Some(origin), Ok(current)) => origin == current,
+            _ => false,
+        };
+        if !same_origin {
+            return;
+        }
+        let path = [property(&anchor, "pathname"), property(&anchor, "search")]
+            .into_iter()
+            .flatten()
+            .collect::<String>();
+        event.prevent_default();
+        let _ = self.try_navigate(&path);
+    }
+}
+
+fn with_router(weak: &Weak<Inner>, f: impl FnOnce(&Router)) {
+    if let Some(inner) = weak.upgrade() {
+        f(&Router { inner });
+    }
+}
+
+/// Get the closest `<a href>` of the event target.
+fn link_of(event: &Event) -> Option<Element> {
+    let node = event.target()?.dyn_into::<Node>().ok()?;
+    let start = match node.dyn_into::<Element>() {
+        Ok(element) => element,
+        Err(node) => node.parent_element()?,
+    };
+    start.find_closest_el("a[href]").ok().flatten()
+}
+
+/// Read a string property of an `<a>`, `None` for an SVG link.
+fn property(anchor: &Element, name: &str) -> Option<String> {
+    js_sys::Reflect::get(anchor, &JsValue::from_str(name))
+        .ok()?
+        .as_string()
+}
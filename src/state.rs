@@ -0,0 +1,228 @@
+//! Shared values telling their subscribers when they change.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+type Subscriber<T> = Rc<dyn Fn(&T)>;
+
+struct Inner<T> {
+    value: RefCell<T>,
+    next_id: Cell<usize>,
+    subscribers: RefCell<Vec<(usize, Subscriber<T>)>>,
+}
+
+/// A value shared through `Rc<RefCell<T>>`, calling its subscribers after every
+/// `set()` or `update()`.
+///
+/// The State is a handle: clones share the same value.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::State;
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let count = State::new(1);
+/// let seen = Rc::new(Cell::new(0));
+/// let subscription = {
+///     let seen = seen.clone();
+///     count.subscribe(move |value| seen.set(*value))
+/// };
+/// count.set(2);
+/// count.update(|value| *value += 1);
+/// assert_eq!(count.get(), 3);
+/// assert_eq!(seen.get(), 3);
+///
+/// drop(subscription);
+/// count.set(10);
+/// assert_eq!(seen.get(), 3);
+/// ```
+pub struct State<T> {
+    inner: Rc<Inner<T>>,
+}
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        State {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for State<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("value", &self.inner.value.borrow())
+            .field("subscribers", &self.inner.subscribers.borrow().len())
+            .finish()
+    }
+}
+
+impl<T: Default + 'static> Default for State<T> {
+    fn default() -> Self {
+        State::new(T::default())
+    }
+}
+
+impl<T: 'static> State<T> {
+    /// A State holding value, without subscribers.
+    pub fn new(value: T) -> Self {
+        State {
+            inner: Rc::new(Inner {
+                value: RefCell::new(value),
+                next_id: Cell::new(0),
+                subscribers: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Read the value without cloning it. The value is borrowed while f runs, so f
+    /// must not call `set()` or `update()` on the same State.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.inner.value.borrow())
+    }
+
+    /// Call closure after every change, until the Subscription is dropped.
+    pub fn subscribe<F: Fn(&T) + 'static>(&self, closure: F) -> Subscription {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner.subscribers.borrow_mut().push((id, Rc::new(closure)));
+        let weak: Weak<Inner<T>> = Rc::downgrade(&self.inner);
+        Subscription {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(inner) = weak.upgrade() {
+                    inner.subscribers.borrow_mut().retain(|(other, _)| *other != id);
+                }
+            })),
+        }
+    }
+}
+
+impl<T: Clone + 'static> State<T> {
+    /// Get a clone of the value.
+    pub fn get(&self) -> T {
+        self.inner.value.borrow().clone()
+    }
+
+    /// Replace the value and call the subscribers.
+    pub fn set(&self, value: T) {
+        *self.inner.value.borrow_mut() = value;
+        self.notify();
+    }
+
+    /// Change a copy of the value and store it, then call the subscribers. Nothing is
+    /// borrowed while f runs, so it may read or set the same State, the copy winning.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.get();
+        f(&mut value);
+        *self.inner.value.borrow_mut() = value;
+        self.notify();
+    }
+
+    fn notify(&self) {
+        // Work on clones, so a subscriber can set the value or subscribe again.
+        let value = self.get();
+        let subscribers: Vec<Subscriber<T>> = self
+            .inner
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, subscriber)| subscriber.clone())
+            .collect();
+        for subscriber in subscribers {
+            subscriber(&value);
+        }
+    }
+}
+
+/// A subscriber of a State, from `subscribe()`.
+///
+/// Dropping the handle removes the subscriber. Call `forget()` to keep it as long as
+/// the State lives.
+#[must_use = "the subscriber is removed as soon as the handle is dropped"]
+pub struct Subscription {
+    unsubscribe: Option<Box<dyn FnOnce()>>,
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("active", &self.unsubscribe.is_some())
+            .finish()
+    }
+}
+
+impl Subscription {
+    /// Keep the subscriber as long as the State lives.
+    pub fn forget(mut self) {
+        self.unsubscribe = None;
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn update_can_use_the_same_state() {
+        let state = State::new(vec![1]);
+        let other = state.clone();
+        state.update(|value| {
+            value.push(other.get().len() + 1);
+            other.set(vec![]);
+        });
+        assert_eq!(state.get(), vec![1, 2]);
+    }
+
+    #[test]
+    fn subscribers_can_set_the_state() {
+        let state = State::new(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _clamp = {
+            let state = state.clone();
+            state.clone().subscribe(move |value| {
+                if *value > 10 {
+                    state.set(10);
+                }
+            })
+        };
+        let _log = {
+            let seen = seen.clone();
+            state.subscribe(move |value| seen.borrow_mut().push(*value))
+        };
+        state.set(50);
+        assert_eq!(state.get(), 10);
+        assert_eq!(*seen.borrow(), vec![10, 50]);
+    }
+
+    #[test]
+    fn dropped_and_forgotten_subscriptions() {
+        let state = State::new(String::new());
+        let count = Rc::new(RefCell::new(0));
+        let counter = |count: &Rc<RefCell<i32>>| {
+            let count = count.clone();
+            move |_: &String| *count.borrow_mut() += 1
+        };
+        let dropped = state.subscribe(counter(&count));
+        state.subscribe(counter(&count)).forget();
+        state.set("a".to_owned());
+        drop(dropped);
+        state.set("b".to_owned());
+        assert_eq!(*count.borrow(), 3);
+        let subscription = state.subscribe(counter(&count));
+        drop(state);
+        drop(subscription);
+    }
+}
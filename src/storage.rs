@@ -0,0 +1,248 @@
+//! Typed localStorage and sessionStorage, with the values stored as JSON.
+//!
+//! Every method returns a `Result`: storage is disabled in some private modes and
+//! full when the quota is exceeded, both are common enough not to panic.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use Minimal::storage;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Settings {
+//!     dark: bool,
+//! }
+//!
+//! # fn main() -> Result<(), Minimal::MinimalError> {
+//! let store = storage::local()?.with_prefix("my-app:");
+//! store.set("settings", &Settings { dark: true })?;
+//! let settings: Option<Settings> = store.get("settings")?;
+//! for key in store.keys()? {
+//!     println!("{}", key);
+//! }
+//! store
+//!     .on_change(|change| println!("{:?} changed in another tab", change.key))?
+//!     .forget();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{short_type_name, MinimalError, Target};
+use crate::event::{EventListenerHandle, MinimalEvents};
+use crate::utils::window;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, StorageEvent};
+
+/// Get localStorage, kept after the browser is closed.
+pub fn local() -> Result<Storage, MinimalError> {
+    window()
+        .local_storage()
+        .map_err(|e| MinimalError::js(Target::Member("local_storage"), e))?
+        .map(Storage::new)
+        .ok_or(MinimalError::NotFound(Target::Member("local_storage")))
+}
+
+/// Get sessionStorage, kept as long as the tab is open.
+pub fn session() -> Result<Storage, MinimalError> {
+    window()
+        .session_storage()
+        .map_err(|e| MinimalError::js(Target::Member("session_storage"), e))?
+        .map(Storage::new)
+        .ok_or(MinimalError::NotFound(Target::Member("session_storage")))
+}
+
+/// A localStorage or sessionStorage, where every key starts with a prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Storage {
+    storage: web_sys::Storage,
+    prefix: String,
+}
+
+impl Storage {
+    fn new(storage: web_sys::Storage) -> Self {
+        Storage {
+            storage,
+            prefix: String::new(),
+        }
+    }
+
+    /// Get the same storage with prefix added to the keys, like `my-app:`.
+    pub fn with_prefix(&self, prefix: &str) -> Storage {
+        Storage {
+            storage: self.storage.clone(),
+            prefix: format!("{}{}", self.prefix, prefix),
+        }
+    }
+
+    /// Get the prefix of the keys.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the wrapped web_sys Storage.
+    pub fn inner(&self) -> &web_sys::Storage {
+        &self.storage
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Get the value of key deserialized from JSON, `None` if there is none.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, MinimalError> {
+        let key = self.full_key(key);
+        match self.get_raw_full(&key)? {
+            Some(raw) => serde_json::from_str(&raw)
+                .map(Some)
+                .map_err(|_| MinimalError::Parse {
+                    target: Target::key(&key),
+                    expected: short_type_name::<T>(),
+                    found: raw,
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the value of key serialized as JSON, or an error if the quota is exceeded.
+    pub fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<(), MinimalError> {
+        let json = serde_json::to_string(value).map_err(|e| MinimalError::Parse {
+            target: Target::key(&self.full_key(key)),
+            expected: "JSON",
+            found: e.to_string(),
+        })?;
+        self.set_raw(key, &json)
+    }
+
+    /// Get the string of key as it is stored.
+    pub fn get_raw(&self, key: &str) -> Result<Option<String>, MinimalError> {
+        self.get_raw_full(&self.full_key(key))
+    }
+
+    fn get_raw_full(&self, key: &str) -> Result<Option<String>, MinimalError> {
+        self.storage
+            .get_item(key)
+            .map_err(|e| MinimalError::js(Target::key(key), e))
+    }
+
+    /// Set the string of key as it is, or an error if the quota is exceeded.
+    pub fn set_raw(&self, key: &str, value: &str) -> Result<(), MinimalError> {
+        let key = self.full_key(key);
+        self.storage
+            .set_item(&key, value)
+            .map_err(|e| MinimalError::js(Target::key(&key), e))
+    }
+
+    /// Remove key.
+    pub fn remove(&self, key: &str) -> Result<(), MinimalError> {
+        let key = self.full_key(key);
+        self.storage
+            .remove_item(&key)
+            .map_err(|e| MinimalError::js(Target::key(&key), e))
+    }
+
+    /// Check if key has a value.
+    pub fn contains(&self, key: &str) -> Result<bool, MinimalError> {
+        self.get_raw(key).map(|value| value.is_some())
+    }
+
+    /// Get the keys with the prefix, without it.
+    pub fn keys(&self) -> Result<Vec<String>, MinimalError> {
+        let length = self
+            .storage
+            .length()
+            .map_err(|e| MinimalError::js(Target::Member("length"), e))?;
+        let mut keys = Vec::new();
+        for index in 0..length {
+            let key = self
+                .storage
+                .key(index)
+                .map_err(|e| MinimalError::js(Target::Index(index), e))?;
+            if let Some(key) = key.as_deref().and_then(|key| key.strip_prefix(&self.prefix)) {
+                keys.push(key.to_owned());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Remove every key with the prefix, or the whole storage without prefix.
+    pub fn clear(&self) -> Result<(), MinimalError> {
+        if self.prefix.is_empty() {
+            return self
+                .storage
+                .clear()
+                .map_err(|e| MinimalError::js(Target::Member("clear"), e));
+        }
+        for key in self.keys()? {
+            self.remove(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Listen to the changes made to this storage by the other tabs, for the keys
+    /// with the prefix. The tab making a change is not told.
+    pub fn on_change<F: FnMut(StorageChange) + 'static>(&self, mut closure: F) -> Result<EventListenerHandle, MinimalError> {
+        let storage = self.storage.clone();
+        let prefix = self.prefix.clone();
+        window().try_on("storage", move |event: Event| {
+            let event = match event.dyn_into::<StorageEvent>() {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+            if event.storage_area().as_ref() != Some(&storage) {
+                return;
+            }
+            let key = match event.key() {
+                Some(key) => match key.strip_prefix(&prefix) {
+                    Some(key) => Some(key.to_owned()),
+                    None => return,
+                },
+                // The whole storage was cleared.
+                None => None,
+            };
+            closure(StorageChange {
+                key,
+                old_value: event.old_value(),
+                new_value: event.new_value(),
+            });
+        })
+    }
+}
+
+/// A change made to a Storage by another tab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The key without the prefix, `None` when the whole storage was cleared.
+    pub key: Option<String>,
+    /// The JSON before the change, `None` for a new key.
+    pub old_value: Option<String>,
+    /// The JSON after the change, `None` for a removed key.
+    pub new_value: Option<String>,
+}
+
+impl StorageChange {
+    /// Get the new value deserialized from JSON, `None` for a removed key.
+    pub fn new_as<T: DeserializeOwned>(&self) -> Result<Option<T>, MinimalError> {
+        self.parse(&self.new_value)
+    }
+
+    /// Get the old value deserialized from JSON, `None` for a new key.
+    pub fn old_as<T: DeserializeOwned>(&self) -> Result<Option<T>, MinimalError> {
+        self.parse(&self.old_value)
+    }
+
+    fn parse<T: DeserializeOwned>(&self, value: &Option<String>) -> Result<Option<T>, MinimalError> {
+        match value {
+            Some(raw) => serde_json::from_str(raw)
+                .map(Some)
+                .map_err(|_| MinimalError::Parse {
+                    target: Target::key(self.key.as_deref().unwrap_or_default()),
+                    expected: short_type_name::<T>(),
+                    found: raw.clone(),
+                }),
+            None => Ok(None),
+        }
+    }
+}
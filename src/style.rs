@@ -0,0 +1,801 @@
+//! Typed CSS values, written with `Display` and read back with `FromStr`.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// Error of parsing a CSS value into Length, Color, Display or Transform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCssError {
+    expected: &'static str,
+    found: String,
+}
+
+impl ParseCssError {
+    fn new(expected: &'static str, found: &str) -> Self {
+        ParseCssError {
+            expected,
+            found: found.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseCssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a CSS {}, found {:?}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for ParseCssError {}
+
+/// Split `12.5px` into `12.5` and `px`.
+fn split_number(value: &str) -> Option<(f64, &str)> {
+    let bytes = value.as_bytes();
+    let mut end = 0;
+    if matches!(bytes.first(), Some(b'-' | b'+')) {
+        end = 1;
+    }
+    while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+        end += 1;
+    }
+    // An exponent like `6.1e-17`, but not the `e` of `em`.
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent = end + 1;
+        if matches!(bytes.get(exponent), Some(b'-' | b'+')) {
+            exponent += 1;
+        }
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            end = exponent;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+        }
+    }
+    let number = value[..end].parse().ok()?;
+    Some((number, &value[end..]))
+}
+
+/// A CSS length, like `12px`, `1.5rem` or `50%`.
+///
+/// The arithmetic operators with a `f64` change the number and keep the unit, so the
+/// computed lengths, always in `px`, can be measured and written back.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::Length;
+///
+/// let width: Length = "120px".parse().unwrap();
+/// assert_eq!(width + 30.0, Length::Px(150.0));
+/// assert_eq!((width / 2.0).to_string(), "60px");
+/// assert_eq!(Length::Rem(1.5).checked_add(Length::Px(2.0)), None);
+/// assert!(Length::Px(100.0) > Length::Px(20.0));
+/// assert_eq!(Length::Px(100.0).partial_cmp(&Length::Em(1.0)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Em(f64),
+    Rem(f64),
+    Percent(f64),
+    Vw(f64),
+    Vh(f64),
+    Vmin(f64),
+    Vmax(f64),
+    Auto,
+}
+
+/// Only lengths of the same unit compare, `1em` being neither more nor less than
+/// `100px`.
+impl PartialOrd for Length {
+    fn partial_cmp(&self, other: &Length) -> Option<std::cmp::Ordering> {
+        if self.unit() != other.unit() {
+            return None;
+        }
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => Some(std::cmp::Ordering::Equal),
+        }
+    }
+}
+
+impl Length {
+    /// Get the number of the length, or `None` for `auto`.
+    pub fn value(&self) -> Option<f64> {
+        match *self {
+            Length::Px(v)
+            | Length::Em(v)
+            | Length::Rem(v)
+            | Length::Percent(v)
+            | Length::Vw(v)
+            | Length::Vh(v)
+            | Length::Vmin(v)
+            | Length::Vmax(v) => Some(v),
+            Length::Auto => None,
+        }
+    }
+
+    /// Get the number of pixels, or `None` if the length is not in `px`.
+    pub fn to_px(&self) -> Option<f64> {
+        match *self {
+            Length::Px(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Apply f to the number, keeping the unit.
+    pub fn map(self, f: impl FnOnce(f64) -> f64) -> Length {
+        match self {
+            Length::Px(v) => Length::Px(f(v)),
+            Length::Em(v) => Length::Em(f(v)),
+            Length::Rem(v) => Length::Rem(f(v)),
+            Length::Percent(v) => Length::Percent(f(v)),
+            Length::Vw(v) => Length::Vw(f(v)),
+            Length::Vh(v) => Length::Vh(f(v)),
+            Length::Vmin(v) => Length::Vmin(f(v)),
+            Length::Vmax(v) => Length::Vmax(f(v)),
+            Length::Auto => Length::Auto,
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            Length::Px(_) => "px",
+            Length::Em(_) => "em",
+            Length::Rem(_) => "rem",
+            Length::Percent(_) => "%",
+            Length::Vw(_) => "vw",
+            Length::Vh(_) => "vh",
+            Length::Vmin(_) => "vmin",
+            Length::Vmax(_) => "vmax",
+            Length::Auto => "",
+        }
+    }
+
+    /// Add two lengths of the same unit, or `None` if the units differ.
+    pub fn checked_add(self, other: Length) -> Option<Length> {
+        match other.value() {
+            Some(v) if self.unit() == other.unit() => Some(self.map(|s| s + v)),
+            _ => None,
+        }
+    }
+
+    /// Subtract two lengths of the same unit, or `None` if the units differ.
+    pub fn checked_sub(self, other: Length) -> Option<Length> {
+        self.checked_add(-other)
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value() {
+            Some(v) => write!(f, "{}{}", v, self.unit()),
+            None => f.write_str("auto"),
+        }
+    }
+}
+
+impl FromStr for Length {
+    type Err = ParseCssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if value.eq_ignore_ascii_case("auto") {
+            return Ok(Length::Auto);
+        }
+        let error = || ParseCssError::new("length", s);
+        let (number, unit) = split_number(value).ok_or_else(error)?;
+        match unit.to_ascii_lowercase().as_str() {
+            "px" => Ok(Length::Px(number)),
+            "em" => Ok(Length::Em(number)),
+            "rem" => Ok(Length::Rem(number)),
+            "%" => Ok(Length::Percent(number)),
+            "vw" => Ok(Length::Vw(number)),
+            "vh" => Ok(Length::Vh(number)),
+            "vmin" => Ok(Length::Vmin(number)),
+            "vmax" => Ok(Length::Vmax(number)),
+            // A unitless length is only valid for zero.
+            "" if number == 0.0 => Ok(Length::Px(0.0)),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl Add<f64> for Length {
+    type Output = Length;
+
+    fn add(self, rhs: f64) -> Length {
+        self.map(|v| v + rhs)
+    }
+}
+
+impl Sub<f64> for Length {
+    type Output = Length;
+
+    fn sub(self, rhs: f64) -> Length {
+        self.map(|v| v - rhs)
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Length;
+
+    fn mul(self, rhs: f64) -> Length {
+        self.map(|v| v * rhs)
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Length;
+
+    fn div(self, rhs: f64) -> Length {
+        self.map(|v| v / rhs)
+    }
+}
+
+impl Neg for Length {
+    type Output = Length;
+
+    fn neg(self) -> Length {
+        self.map(|v| -v)
+    }
+}
+
+/// A CSS color in RGB with an alpha between 0 and 1.
+///
+/// It parses hex colors, `rgb()`, `rgba()` and `transparent`, which covers what
+/// `getComputedStyle` returns.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::Color;
+///
+/// let color: Color = "#ff8000".parse().unwrap();
+/// assert_eq!(color, Color::rgb(255, 128, 0));
+/// assert_eq!(color.with_alpha(0.5).to_string(), "rgba(255, 128, 0, 0.5)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: f64,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0.0);
+
+    /// An opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 1.0 }
+    }
+
+    /// A color with alpha, clamped between 0 and 1 when written.
+    pub const fn rgba(r: u8, g: u8, b: u8, a: f64) -> Color {
+        Color { r, g, b, a }
+    }
+
+    /// Get the same color with another alpha.
+    pub fn with_alpha(self, a: f64) -> Color {
+        Color { a, ..self }
+    }
+
+    fn parse_hex(hex: &str) -> Option<Color> {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let pair = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        match hex.len() {
+            3 | 4 => {
+                let a = if hex.len() == 4 { digit(3)? * 17 } else { 255 };
+                Some(Color::rgba(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17, a as f64 / 255.0))
+            }
+            6 | 8 => {
+                let a = if hex.len() == 8 { pair(6)? } else { 255 };
+                Some(Color::rgba(pair(0)?, pair(2)?, pair(4)?, a as f64 / 255.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse the inside of `rgb()`, with commas or the `r g b / a` syntax.
+    fn parse_function(args: &str) -> Option<Color> {
+        let args: Vec<&str> = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .collect();
+        let channel = |arg: &str| -> Option<u8> {
+            let value = match arg.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().ok()? / 100.0 * 255.0,
+                None => arg.parse::<f64>().ok()?,
+            };
+            Some(value.round().clamp(0.0, 255.0) as u8)
+        };
+        let alpha = match args.get(3) {
+            Some(arg) => match arg.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+                None => arg.parse::<f64>().ok()?,
+            },
+            None => 1.0,
+        };
+        // An alpha out of range is clamped, as CSS does.
+        let alpha = alpha.clamp(0.0, 1.0);
+        if args.len() < 3 || args.len() > 4 {
+            return None;
+        }
+        Some(Color::rgba(channel(args[0])?, channel(args[1])?, channel(args[2])?, alpha))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let a = self.a.clamp(0.0, 1.0);
+        if a == 1.0 {
+            write!(f, "rgb({}, {}, {})", self.r, self.g, self.b)
+        } else {
+            write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, a)
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseCssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_ascii_lowercase();
+        let color = if value == "transparent" {
+            Some(Color::TRANSPARENT)
+        } else if let Some(hex) = value.strip_prefix('#') {
+            Color::parse_hex(hex)
+        } else {
+            value
+                .strip_prefix("rgba(")
+                .or_else(|| value.strip_prefix("rgb("))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(Color::parse_function)
+        };
+        color.ok_or_else(|| ParseCssError::new("color", s))
+    }
+}
+
+/// The CSS `display` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Display {
+    None,
+    Block,
+    Inline,
+    InlineBlock,
+    Flex,
+    InlineFlex,
+    Grid,
+    InlineGrid,
+    FlowRoot,
+    Contents,
+    ListItem,
+    Table,
+    TableRow,
+    TableCell,
+}
+
+impl Display {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Display::None => "none",
+            Display::Block => "block",
+            Display::Inline => "inline",
+            Display::InlineBlock => "inline-block",
+            Display::Flex => "flex",
+            Display::InlineFlex => "inline-flex",
+            Display::Grid => "grid",
+            Display::InlineGrid => "inline-grid",
+            Display::FlowRoot => "flow-root",
+            Display::Contents => "contents",
+            Display::ListItem => "list-item",
+            Display::Table => "table",
+            Display::TableRow => "table-row",
+            Display::TableCell => "table-cell",
+        }
+    }
+}
+
+impl fmt::Display for Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Display {
+    type Err = ParseCssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ALL: [Display; 14] = [
+            Display::None,
+            Display::Block,
+            Display::Inline,
+            Display::InlineBlock,
+            Display::Flex,
+            Display::InlineFlex,
+            Display::Grid,
+            Display::InlineGrid,
+            Display::FlowRoot,
+            Display::Contents,
+            Display::ListItem,
+            Display::Table,
+            Display::TableRow,
+            Display::TableCell,
+        ];
+        let value = s.trim();
+        ALL.into_iter()
+            .find(|display| display.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| ParseCssError::new("display", s))
+    }
+}
+
+/// One function of a CSS transform. Angles are in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransformFn {
+    Translate(Length, Length),
+    Scale(f64, f64),
+    Rotate(f64),
+    SkewX(f64),
+    SkewY(f64),
+    /// The 2D matrix `matrix(a, b, c, d, e, f)`, as returned by `getComputedStyle`.
+    Matrix([f64; 6]),
+}
+
+impl TransformFn {
+    /// Get the function as a 2D matrix, or `None` for a translation not in `px`.
+    fn to_matrix(self) -> Option<[f64; 6]> {
+        Some(match self {
+            TransformFn::Translate(x, y) => [1.0, 0.0, 0.0, 1.0, x.to_px()?, y.to_px()?],
+            TransformFn::Scale(x, y) => [x, 0.0, 0.0, y, 0.0, 0.0],
+            TransformFn::Rotate(deg) => {
+                let (sin, cos) = deg.to_radians().sin_cos();
+                [cos, sin, -sin, cos, 0.0, 0.0]
+            }
+            TransformFn::SkewX(deg) => [1.0, 0.0, deg.to_radians().tan(), 1.0, 0.0, 0.0],
+            TransformFn::SkewY(deg) => [1.0, deg.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            TransformFn::Matrix(m) => m,
+        })
+    }
+}
+
+impl fmt::Display for TransformFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformFn::Translate(x, y) => write!(f, "translate({}, {})", x, y),
+            TransformFn::Scale(x, y) => write!(f, "scale({}, {})", x, y),
+            TransformFn::Rotate(deg) => write!(f, "rotate({}deg)", deg),
+            TransformFn::SkewX(deg) => write!(f, "skewX({}deg)", deg),
+            TransformFn::SkewY(deg) => write!(f, "skewY({}deg)", deg),
+            TransformFn::Matrix([a, b, c, d, e, g]) => {
+                write!(f, "matrix({}, {}, {}, {}, {}, {})", a, b, c, d, e, g)
+            }
+        }
+    }
+}
+
+/// A CSS transform, a list of functions applied in order. An empty one is `none`.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::{Length, Transform};
+///
+/// let transform = Transform::new().translate(Length::Px(10.0), Length::Px(0.0)).scale(2.0);
+/// assert_eq!(transform.to_string(), "translate(10px, 0px) scale(2, 2)");
+///
+/// let computed: Transform = "matrix(2, 0, 0, 2, 10, 0)".parse().unwrap();
+/// assert_eq!(computed.translation(), Some((10.0, 0.0)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transform(pub Vec<TransformFn>);
+
+impl Transform {
+    /// An empty transform, `none`.
+    pub fn new() -> Self {
+        Transform(Vec::new())
+    }
+
+    fn then(mut self, function: TransformFn) -> Self {
+        self.0.push(function);
+        self
+    }
+
+    /// Add `translate(x, y)`.
+    pub fn translate(self, x: Length, y: Length) -> Self {
+        self.then(TransformFn::Translate(x, y))
+    }
+
+    /// Add `scale(factor)`.
+    pub fn scale(self, factor: f64) -> Self {
+        self.then(TransformFn::Scale(factor, factor))
+    }
+
+    /// Add `scale(x, y)`.
+    pub fn scale_xy(self, x: f64, y: f64) -> Self {
+        self.then(TransformFn::Scale(x, y))
+    }
+
+    /// Add `rotate()` in degrees.
+    pub fn rotate(self, deg: f64) -> Self {
+        self.then(TransformFn::Rotate(deg))
+    }
+
+    /// Add `skewX()` in degrees.
+    pub fn skew_x(self, deg: f64) -> Self {
+        self.then(TransformFn::SkewX(deg))
+    }
+
+    /// Add `skewY()` in degrees.
+    pub fn skew_y(self, deg: f64) -> Self {
+        self.then(TransformFn::SkewY(deg))
+    }
+
+    /// Get the whole transform as one 2D matrix `[a, b, c, d, e, f]`, or `None` if a
+    /// translation is not in `px`.
+    pub fn to_matrix(&self) -> Option<[f64; 6]> {
+        self.0.iter().try_fold([1.0, 0.0, 0.0, 1.0, 0.0, 0.0], |m, function| {
+            let [a, b, c, d, e, f] = function.to_matrix()?;
+            Some([
+                m[0] * a + m[2] * b,
+                m[1] * a + m[3] * b,
+                m[0] * c + m[2] * d,
+                m[1] * c + m[3] * d,
+                m[0] * e + m[2] * f + m[4],
+                m[1] * e + m[3] * f + m[5],
+            ])
+        })
+    }
+
+    /// Get the translation in px of the whole transform.
+    pub fn translation(&self) -> Option<(f64, f64)> {
+        self.to_matrix().map(|m| (m[4], m[5]))
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("none");
+        }
+        for (i, function) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the `)` closing the `(` at open, skipping nested ones like `calc(...)`.
+fn closing_paren(value: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in value[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split the arguments of a function on commas and spaces outside of nested parentheses.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && (c == ',' || c.is_whitespace()) => {
+                parts.push(&args[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// Parse an angle to degrees, a bare number being degrees too.
+fn parse_angle(value: &str) -> Option<f64> {
+    let (number, unit) = split_number(value)?;
+    match unit {
+        "deg" | "" => Some(number),
+        "rad" => Some(number.to_degrees()),
+        "grad" => Some(number * 0.9),
+        "turn" => Some(number * 360.0),
+        _ => None,
+    }
+}
+
+impl FromStr for Transform {
+    type Err = ParseCssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseCssError::new("transform", s);
+        let mut rest = s.trim();
+        let mut transform = Transform::new();
+        if rest.eq_ignore_ascii_case("none") {
+            return Ok(transform);
+        }
+        while !rest.is_empty() {
+            let open = rest.find('(').ok_or_else(error)?;
+            let close = closing_paren(rest, open).ok_or_else(error)?;
+            let name = rest[..open].trim();
+            if name.contains(')') {
+                return Err(error());
+            }
+            let args = split_args(&rest[open + 1..close]);
+            let length = |i: usize| args.get(i).and_then(|arg| arg.parse::<Length>().ok());
+            let number = |i: usize| args.get(i).and_then(|arg| arg.parse::<f64>().ok());
+            let angle = |i: usize| args.get(i).and_then(|arg| parse_angle(arg));
+            let function = match (name, args.len()) {
+                ("translate", 1) => length(0).map(|x| TransformFn::Translate(x, Length::Px(0.0))),
+                ("translate", 2) => length(0).zip(length(1)).map(|(x, y)| TransformFn::Translate(x, y)),
+                ("translateX", 1) => length(0).map(|x| TransformFn::Translate(x, Length::Px(0.0))),
+                ("translateY", 1) => length(0).map(|y| TransformFn::Translate(Length::Px(0.0), y)),
+                ("scale", 1) => number(0).map(|s| TransformFn::Scale(s, s)),
+                ("scale", 2) => number(0).zip(number(1)).map(|(x, y)| TransformFn::Scale(x, y)),
+                ("scaleX", 1) => number(0).map(|x| TransformFn::Scale(x, 1.0)),
+                ("scaleY", 1) => number(0).map(|y| TransformFn::Scale(1.0, y)),
+                ("rotate", 1) => angle(0).map(TransformFn::Rotate),
+                ("skewX", 1) => angle(0).map(TransformFn::SkewX),
+                ("skewY", 1) => angle(0).map(TransformFn::SkewY),
+                ("matrix", 6) => {
+                    let mut m = [0.0; 6];
+                    for (i, value) in m.iter_mut().enumerate() {
+                        *value = number(i).ok_or_else(error)?;
+                    }
+                    Some(TransformFn::Matrix(m))
+                }
+                _ => None,
+            };
+            transform.0.push(function.ok_or_else(error)?);
+            rest = rest[close + 1..].trim_start();
+        }
+        Ok(transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, Display, Length, ParseCssError, Transform, TransformFn};
+
+    #[test]
+    fn lengths() {
+        assert_eq!("12.5px".parse(), Ok(Length::Px(12.5)));
+        assert_eq!("  -2EM ".parse(), Ok(Length::Em(-2.0)));
+        assert_eq!("50%".parse(), Ok(Length::Percent(50.0)));
+        assert_eq!("1e2vw".parse(), Ok(Length::Vw(100.0)));
+        assert_eq!("AUTO".parse(), Ok(Length::Auto));
+        assert_eq!("0".parse(), Ok(Length::Px(0.0)));
+        assert_eq!(Length::Rem(1.5).to_string(), "1.5rem");
+        assert_eq!(Length::Auto.to_string(), "auto");
+    }
+
+    #[test]
+    fn invalid_lengths() {
+        for value in ["12", "12pt", "px", "", "1.2.3px", "calc(1px + 2em)", "12 px"] {
+            assert!(value.parse::<Length>().is_err(), "{:?}", value);
+        }
+        assert_eq!(
+            "3furlongs".parse::<Length>().unwrap_err().to_string(),
+            "expected a CSS length, found \"3furlongs\""
+        );
+    }
+
+    #[test]
+    fn length_arithmetic_and_order() {
+        assert_eq!(Length::Px(10.0).checked_add(Length::Px(5.0)), Some(Length::Px(15.0)));
+        assert_eq!(Length::Px(10.0).checked_sub(Length::Em(5.0)), None);
+        assert_eq!(Length::Auto.checked_add(Length::Auto), None);
+        assert_eq!(-Length::Vh(3.0) * 2.0, Length::Vh(-6.0));
+        assert!(Length::Em(1.0) < Length::Em(2.0));
+        assert_eq!(Length::Px(100.0).partial_cmp(&Length::Em(1.0)), None);
+        assert_eq!(Length::Auto.partial_cmp(&Length::Auto), Some(std::cmp::Ordering::Equal));
+        assert_eq!(Length::Auto.partial_cmp(&Length::Px(0.0)), None);
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!("#FF8000".parse(), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!("#f80".parse(), Ok(Color::rgb(255, 136, 0)));
+        assert_eq!("#F808".parse(), Ok(Color::rgba(255, 136, 0, 136.0 / 255.0)));
+        assert_eq!("#00000000".parse(), Ok(Color::TRANSPARENT));
+        assert_eq!(" #fff ".parse(), Ok(Color::WHITE));
+        for value in ["#", "#ff", "#fffff", "#ggg", "#1234567", "fff", "#ff80001"] {
+            assert!(value.parse::<Color>().is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rgb_colors() {
+        assert_eq!("rgb(255, 128, 0)".parse(), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!("RGB(255 128 0)".parse(), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!("rgb(100%, 0%, 50%)".parse(), Ok(Color::rgb(255, 0, 128)));
+        assert_eq!("rgb(300, -5, 0)".parse(), Ok(Color::rgb(255, 0, 0)));
+        assert_eq!("rgba(1, 2, 3, 0.25)".parse(), Ok(Color::rgba(1, 2, 3, 0.25)));
+        assert_eq!("rgb(1 2 3 / 50%)".parse(), Ok(Color::rgba(1, 2, 3, 0.5)));
+        assert_eq!("transparent".parse(), Ok(Color::TRANSPARENT));
+        for value in ["rgb(1, 2)", "rgb(1, 2, 3, 4, 5)", "rgb(a, b, c)", "rgb(1, 2, 3", "hsl(0, 0%, 0%)", "red"] {
+            assert!(value.parse::<Color>().is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rgba_alpha_is_clamped() {
+        assert_eq!("rgba(0, 0, 0, 1.5)".parse::<Color>().map(|c| c.a), Ok(1.0));
+        assert_eq!("rgba(0, 0, 0, -1)".parse::<Color>().map(|c| c.a), Ok(0.0));
+        assert_eq!("rgba(0, 0, 0, 150%)".parse::<Color>().map(|c| c.a), Ok(1.0));
+        assert_eq!(Color::rgba(1, 2, 3, 2.0).to_string(), "rgb(1, 2, 3)");
+        assert_eq!(Color::rgba(1, 2, 3, -2.0).to_string(), "rgba(1, 2, 3, 0)");
+        assert!("rgba(0, 0, 0, x)".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn displays() {
+        assert_eq!(" Inline-Flex ".parse(), Ok(Display::InlineFlex));
+        assert_eq!("none".parse(), Ok(Display::None));
+        assert_eq!(Display::TableCell.to_string(), "table-cell");
+        assert_eq!(
+            "inline flex".parse::<Display>(),
+            Err(ParseCssError::new("display", "inline flex"))
+        );
+        assert!("".parse::<Display>().is_err());
+    }
+
+    #[test]
+    fn transforms() {
+        let transform: Transform = "translate(10px) rotate(0.5turn)  scale(2,3) skewX(1rad)".parse().unwrap();
+        assert_eq!(transform.0.len(), 4);
+        assert_eq!(transform.0[0], TransformFn::Translate(Length::Px(10.0), Length::Px(0.0)));
+        assert_eq!(transform.0[1], TransformFn::Rotate(180.0));
+        assert_eq!(transform.0[2], TransformFn::Scale(2.0, 3.0));
+        assert_eq!("NONE".parse(), Ok(Transform::new()));
+        assert_eq!(Transform::new().to_string(), "none");
+        let matrix: Transform = "matrix(1, 0, 0, 1, 6.1e-17, -4)".parse().unwrap();
+        assert_eq!(matrix.translation(), Some((6.1e-17, -4.0)));
+        assert_eq!(Transform::new().translate(Length::Em(1.0), Length::Px(0.0)).translation(), None);
+    }
+
+    #[test]
+    fn invalid_transforms() {
+        for value in [
+            "",
+            "translate",
+            "translate(10px",
+            "translate)10px(",
+            "spin(10deg)",
+            "rotate(10px)",
+            "matrix(1, 0, 0, 1, 0)",
+            "scale(2) )",
+        ] {
+            if value.is_empty() {
+                assert_eq!(value.parse(), Ok(Transform::new()));
+            } else {
+                assert!(value.parse::<Transform>().is_err(), "{:?}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn nested_parentheses_stay_in_their_function() {
+        // calc() is not a Length, but it must not cut the function short.
+        let error = "translate(calc(10px + 1em), 0) scale(2)".parse::<Transform>().unwrap_err();
+        assert_eq!(error, ParseCssError::new("transform", "translate(calc(10px + 1em), 0) scale(2)"));
+        assert_eq!(super::split_args("calc(10px + 1em), 0"), vec!["calc(10px + 1em)", "0"]);
+        assert_eq!(super::closing_paren("a(b(c)) d()", 1), Some(6));
+    }
+}
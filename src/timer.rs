@@ -0,0 +1,255 @@
+//! Timers and animation frames cancelled when their handle is dropped.
+
+use crate::error::{MinimalError, Target};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::prelude::*;
+use web_sys::Window;
+
+/// Clamp milliseconds to the i32 of the JavaScript timers.
+fn millis(ms: u32) -> i32 {
+    ms.min(i32::MAX as u32) as i32
+}
+
+/// A timer from `set_timeout()`.
+///
+/// Dropping the handle cancels the timer, if it did not fire yet, and frees the wasm
+/// Closure. Call `forget()` to let it fire anyway.
+#[must_use = "the timer is cancelled as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct Timeout {
+    window: Window,
+    id: i32,
+    closure: Option<Closure<dyn FnMut()>>,
+}
+
+impl Timeout {
+    pub(crate) fn new<F: FnOnce() + 'static>(window: &Window, ms: u32, closure: F) -> Result<Self, MinimalError> {
+        let mut closure = Some(closure);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            if let Some(closure) = closure.take() {
+                closure();
+            }
+        });
+        let id = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), millis(ms))
+            .map_err(|e| MinimalError::js(Target::Member("set_timeout"), e))?;
+        Ok(Timeout {
+            window: window.clone(),
+            id,
+            closure: Some(closure),
+        })
+    }
+
+    /// Let the timer fire, handing the Closure to the JavaScript garbage collector.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            drop(closure.into_js_value());
+        }
+    }
+
+    /// Cancel the timer, same as dropping the handle.
+    pub fn cancel(self) {}
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            self.window.clear_timeout_with_handle(self.id);
+        }
+    }
+}
+
+/// A timer from `set_interval()`.
+///
+/// Dropping the handle stops the timer and frees the wasm Closure. Call `forget()` to
+/// keep it for the whole life of the page.
+#[must_use = "the timer is stopped as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct Interval {
+    window: Window,
+    id: i32,
+    closure: Option<Closure<dyn FnMut()>>,
+}
+
+impl Interval {
+    pub(crate) fn new<F: FnMut() + 'static>(window: &Window, ms: u32, closure: F) -> Result<Self, MinimalError> {
+        let closure = Closure::<dyn FnMut()>::new(closure);
+        let id = window
+            .set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), millis(ms))
+            .map_err(|e| MinimalError::js(Target::Member("set_interval"), e))?;
+        Ok(Interval {
+            window: window.clone(),
+            id,
+            closure: Some(closure),
+        })
+    }
+
+    /// Keep the timer forever, handing the Closure to the JavaScript garbage collector.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            drop(closure.into_js_value());
+        }
+    }
+
+    /// Stop the timer, same as dropping the handle.
+    pub fn cancel(self) {}
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            self.window.clear_interval_with_handle(self.id);
+        }
+    }
+}
+
+/// A callback from `request_frame()`, called before the next repaint.
+///
+/// Dropping the handle cancels the callback, if it was not called yet.
+#[must_use = "the callback is cancelled as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct AnimationFrame {
+    window: Window,
+    id: i32,
+    closure: Option<Closure<dyn FnMut(f64)>>,
+}
+
+impl AnimationFrame {
+    pub(crate) fn new<F: FnOnce(f64) + 'static>(window: &Window, closure: F) -> Result<Self, MinimalError> {
+        let mut closure = Some(closure);
+        let closure = Closure::<dyn FnMut(f64)>::new(move |time: f64| {
+            if let Some(closure) = closure.take() {
+                closure(time);
+            }
+        });
+        let id = window
+            .request_animation_frame(closure.as_ref().unchecked_ref())
+            .map_err(|e| MinimalError::js(Target::Member("request_animation_frame"), e))?;
+        Ok(AnimationFrame {
+            window: window.clone(),
+            id,
+            closure: Some(closure),
+        })
+    }
+
+    /// Let the callback run, handing the Closure to the JavaScript garbage collector.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            drop(closure.into_js_value());
+        }
+    }
+
+    /// Cancel the callback, same as dropping the handle.
+    pub fn cancel(self) {}
+}
+
+impl Drop for AnimationFrame {
+    fn drop(&mut self) {
+        if self.closure.take().is_some() {
+            let _ = self.window.cancel_animation_frame(self.id);
+        }
+    }
+}
+
+struct FrameState {
+    window: Window,
+    id: Option<i32>,
+    last: Option<f64>,
+    closure: Option<Closure<dyn FnMut(f64)>>,
+}
+
+/// A loop from `frame_loop()`, calling its closure on every frame with the
+/// milliseconds since the previous one, 0 on the first frame.
+///
+/// Dropping the handle stops the loop.
+///
+/// # Examples
+///
+/// ```no_run
+/// use Minimal::*;
+///
+/// let window = Minimal::window();
+/// let ball = Minimal::document().query_selector_html(".ball");
+/// let mut x = 0.0;
+/// let animation = window.frame_loop(move |delta| {
+///     x += 0.2 * delta;
+///     ball.set_prop("transform", Transform::new().translate(Length::Px(x), Length::Px(0.0)));
+/// });
+/// window.set_timeout(2000, move || drop(animation)).forget();
+/// ```
+#[must_use = "the loop is stopped as soon as the handle is dropped"]
+pub struct FrameLoop {
+    state: Rc<RefCell<FrameState>>,
+}
+
+impl std::fmt::Debug for FrameLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameLoop")
+            .field("id", &self.state.borrow().id)
+            .finish()
+    }
+}
+
+impl FrameLoop {
+    pub(crate) fn new<F: FnMut(f64) + 'static>(window: &Window, mut closure: F) -> Result<Self, MinimalError> {
+        let state = Rc::new(RefCell::new(FrameState {
+            window: window.clone(),
+            id: None,
+            last: None,
+            closure: None,
+        }));
+        // The Closure only holds a Weak, so dropping the handle frees everything.
+        let weak: Weak<RefCell<FrameState>> = Rc::downgrade(&state);
+        let frame = Closure::<dyn FnMut(f64)>::new(move |time: f64| {
+            let state = match weak.upgrade() {
+                Some(state) => state,
+                None => return,
+            };
+            let delta = {
+                let mut state = state.borrow_mut();
+                let delta = state.last.map_or(0.0, |last| time - last);
+                state.last = Some(time);
+                delta
+            };
+            closure(delta);
+            // The closure may have stopped the loop by dropping the handle.
+            if Rc::strong_count(&state) > 1 {
+                let _ = FrameLoop::request(&state);
+            }
+        });
+        state.borrow_mut().closure = Some(frame);
+        FrameLoop::request(&state)?;
+        Ok(FrameLoop { state })
+    }
+
+    fn request(state: &Rc<RefCell<FrameState>>) -> Result<(), MinimalError> {
+        let mut state = state.borrow_mut();
+        let id = match &state.closure {
+            Some(closure) => state
+                .window
+                .request_animation_frame(closure.as_ref().unchecked_ref())
+                .map_err(|e| MinimalError::js(Target::Member("request_animation_frame"), e))?,
+            None => return Ok(()),
+        };
+        state.id = Some(id);
+        Ok(())
+    }
+
+    /// Keep the loop running for the whole life of the page.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+
+    /// Stop the loop, same as dropping the handle.
+    pub fn cancel(self) {}
+}
+
+impl Drop for FrameLoop {
+    fn drop(&mut self) {
+        let mut state = self.state.borrow_mut();
+        if let Some(id) = state.id.take() {
+            let _ = state.window.cancel_animation_frame(id);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Query strings and relative URLs, parsed and written in Rust.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Decode a `application/x-www-form-urlencoded` component, `+` being a space.
+pub(crate) fn decode(value: &str) -> String {
+    // An encoded `+` is `%2B`, so every `+` left is a space.
+    percent_decode(&value.replace('+', " "))
+}
+
+/// Decode the `%` escapes of a path segment. An invalid escape is kept as it is, as
+/// browsers do.
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = value.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Encode a `application/x-www-form-urlencoded` component, a space being `+`.
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'*' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Encode the characters a path or a hash cannot hold. `%` is kept, so a part that is
+/// already encoded stays the same.
+fn encode_part(value: &str, reserved: &[u8]) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte <= b' ' || byte >= 0x7f || reserved.contains(&byte) {
+            encoded.push_str(&format!("%{:02X}", byte));
+        } else {
+            encoded.push(byte as char);
+        }
+    }
+    encoded
+}
+
+/// The parameters of a query string, where a key can repeat, like `?tag=a&tag=b`.
+///
+/// The order of the parameters is kept. It parses with or without the leading `?`
+/// and writes without it.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::QueryParams;
+///
+/// let mut params: QueryParams = "?tag=a&tag=b&q=hello+world".parse().unwrap();
+/// assert_eq!(params.get("q"), Some("hello world"));
+/// assert_eq!(params.get_all("tag"), vec!["a", "b"]);
+/// params.set("page", 2);
+/// params.remove("tag");
+/// assert_eq!(params.to_string(), "q=hello+world&page=2");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    /// An empty query.
+    pub fn new() -> Self {
+        QueryParams { pairs: Vec::new() }
+    }
+
+    /// Parse a query string, with or without the leading `?`. It cannot fail: a
+    /// pair without `=` has an empty value and an invalid escape is kept as it is.
+    pub fn from_query(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        let pairs = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => (decode(key), decode(value)),
+                None => (decode(pair), String::new()),
+            })
+            .collect();
+        QueryParams { pairs }
+    }
+
+    /// Get the first value of key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get every value of key, in order.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.pairs
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Check if key is in the query.
+    pub fn contains(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    /// Add a value to key, keeping the values it already has.
+    pub fn append(&mut self, key: &str, value: impl fmt::Display) {
+        self.pairs.push((key.to_owned(), value.to_string()));
+    }
+
+    /// Set the only value of key, in place of the first one it had.
+    pub fn set(&mut self, key: &str, value: impl fmt::Display) {
+        let value = value.to_string();
+        match self.pairs.iter().position(|(k, _)| k == key) {
+            Some(index) => {
+                self.pairs[index].1 = value;
+                let mut seen = 0;
+                self.pairs.retain(|(k, _)| {
+                    if k == key {
+                        seen += 1;
+                        seen == 1
+                    } else {
+                        true
+                    }
+                });
+            }
+            None => self.pairs.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Remove every value of key.
+    pub fn remove(&mut self, key: &str) {
+        self.pairs.retain(|(k, _)| k != key);
+    }
+
+    /// Iterate over the keys and values, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Get the number of values.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Check if the query has no value.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl FromStr for QueryParams {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(QueryParams::from_query(s))
+    }
+}
+
+impl fmt::Display for QueryParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str("&")?;
+            }
+            write!(f, "{}={}", encode(key), encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: AsRef<str>, V: fmt::Display> FromIterator<(K, V)> for QueryParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = QueryParams::new();
+        for (key, value) in iter {
+            params.append(key.as_ref(), value);
+        }
+        params
+    }
+}
+
+/// A URL split into its path, query and hash, to build links.
+///
+/// The path is everything before `?` or `#`, so it works with relative links like
+/// `/users` or `../edit` and with absolute ones. When written, the spaces, `#`, `?`
+/// and the other characters a URL cannot hold are percent-encoded in the path and
+/// the hash.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::Url;
+///
+/// let url = Url::new("/list").param("page", 2).param("tag", "a b").hash("results");
+/// assert_eq!(url.to_string(), "/list?page=2&tag=a+b#results");
+/// assert_eq!(Url::new("/my files").hash("part #2").to_string(), "/my%20files#part%20%232");
+///
+/// let parsed: Url = "https://example.com/list?page=2#top".parse().unwrap();
+/// assert_eq!(parsed.path(), "https://example.com/list");
+/// assert_eq!(parsed.query().get("page"), Some("2"));
+/// assert_eq!(parsed.get_hash(), Some("top"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Url {
+    path: String,
+    query: QueryParams,
+    hash: Option<String>,
+}
+
+impl Url {
+    /// A URL to path, without query or hash.
+    pub fn new(path: &str) -> Self {
+        Url {
+            path: path.to_owned(),
+            query: QueryParams::new(),
+            hash: None,
+        }
+    }
+
+    /// Add a query parameter, keeping the values key already has.
+    pub fn param(mut self, key: &str, value: impl fmt::Display) -> Self {
+        self.query.append(key, value);
+        self
+    }
+
+    /// Replace the whole query.
+    pub fn with_query(mut self, query: QueryParams) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Set the hash, without the `#`.
+    pub fn hash(mut self, hash: &str) -> Self {
+        self.hash = Some(hash.strip_prefix('#').unwrap_or(hash).to_owned());
+        self
+    }
+
+    /// Remove the hash.
+    pub fn without_hash(mut self) -> Self {
+        self.hash = None;
+        self
+    }
+
+    /// Get the path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the query.
+    pub fn query(&self) -> &QueryParams {
+        &self.query
+    }
+
+    /// Get the query to change it.
+    pub fn query_mut(&mut self) -> &mut QueryParams {
+        &mut self.query
+    }
+
+    /// Get the hash, without the `#`.
+    pub fn get_hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+}
+
+impl FromStr for Url {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, hash) = match s.split_once('#') {
+            Some((rest, hash)) => (rest, Some(hash.to_owned())),
+            None => (s, None),
+        };
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        Ok(Url {
+            path: path.to_owned(),
+            query: QueryParams::from_query(query),
+            hash,
+        })
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_part(&self.path, b"\"#<>?`{}"))?;
+        if !self.query.is_empty() {
+            write!(f, "?{}", self.query)?;
+        }
+        if let Some(hash) = &self.hash {
+            write!(f, "#{}", encode_part(hash, b"\"#<>`"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryParams, Url};
+
+    fn parse(query: &str) -> QueryParams {
+        query.parse().unwrap()
+    }
+
+    #[test]
+    fn plus_and_percent_20_are_spaces() {
+        let params = parse("a=one+two&b=one%20two&c=1%2B1");
+        assert_eq!(params.get("a"), Some("one two"));
+        assert_eq!(params.get("b"), Some("one two"));
+        assert_eq!(params.get("c"), Some("1+1"));
+        assert_eq!(params.to_string(), "a=one+two&b=one+two&c=1%2B1");
+    }
+
+    #[test]
+    fn repeated_keys_keep_their_order() {
+        let mut params = parse("?tag=b&x=1&tag=a&tag=c");
+        assert_eq!(params.get("tag"), Some("b"));
+        assert_eq!(params.get_all("tag"), vec!["b", "a", "c"]);
+        assert_eq!(params.len(), 4);
+        params.append("tag", "d");
+        assert_eq!(params.get_all("tag"), vec!["b", "a", "c", "d"]);
+        params.set("tag", "z");
+        assert_eq!(params.to_string(), "tag=z&x=1");
+    }
+
+    #[test]
+    fn empty_values_and_keys_without_equal() {
+        let params = parse("a=&b&&c=1&=d");
+        assert_eq!(params.get("a"), Some(""));
+        assert_eq!(params.get("b"), Some(""));
+        assert_eq!(params.get(""), Some("d"));
+        assert_eq!(params.len(), 4);
+        assert!(params.contains("b"));
+        assert_eq!(params.to_string(), "a=&b=&c=1&=d");
+        assert!(parse("").is_empty());
+        assert!(parse("?").is_empty());
+        assert_eq!(QueryParams::from_query("?a=1&b"), parse("a=1&b="));
+    }
+
+    #[test]
+    fn invalid_escapes_are_kept() {
+        let params = parse("a=100%&b=%zz&c=%e2%9c%93");
+        assert_eq!(params.get("a"), Some("100%"));
+        assert_eq!(params.get("b"), Some("%zz"));
+        assert_eq!(params.get("c"), Some("✓"));
+    }
+
+    #[test]
+    fn special_characters_round_trip() {
+        let params: QueryParams = [("q", "a&b=c d"), ("ü", "#?/")].into_iter().collect();
+        let text = params.to_string();
+        assert_eq!(text, "q=a%26b%3Dc+d&%C3%BC=%23%3F%2F");
+        assert_eq!(parse(&text), params);
+    }
+
+    #[test]
+    fn url_parts() {
+        let url: Url = "/a/b?x=1&y=2#top".parse().unwrap();
+        assert_eq!(url.path(), "/a/b");
+        assert_eq!(url.query().get_all("y"), vec!["2"]);
+        assert_eq!(url.get_hash(), Some("top"));
+        assert_eq!(url.to_string(), "/a/b?x=1&y=2#top");
+        let url: Url = "/a#b?c".parse().unwrap();
+        assert_eq!((url.path(), url.get_hash()), ("/a", Some("b?c")));
+        assert!(url.query().is_empty());
+        assert_eq!(Url::new("/a").hash("#b").without_hash().to_string(), "/a");
+    }
+
+    #[test]
+    fn url_path_and_hash_are_encoded() {
+        let url = Url::new("/my docs/a?b#c").hash("one two#three\"");
+        assert_eq!(url.to_string(), "/my%20docs/a%3Fb%23c#one%20two%23three%22");
+        assert_eq!(Url::new("/caf\u{e9}").to_string(), "/caf%C3%A9");
+        // An encoded path is written as it is.
+        let url: Url = "/my%20docs?q=1#a%20b".parse().unwrap();
+        assert_eq!(url.to_string(), "/my%20docs?q=1#a%20b");
+        assert_eq!(Url::new("/a").hash("?x=1/y").to_string(), "/a#?x=1/y");
+    }
+}
@@ -0,0 +1,515 @@
+//! Validation of form fields in Rust, mirrored into the constraint API of the browser
+//! and into ARIA attributes.
+
+use crate::error::{MinimalError, OrPanic, Target};
+use crate::form::{controls, is_disabled, Control, MinimalForm};
+use crate::url::QueryParams;
+use js_sys::{Array, Function, Reflect, RegExp};
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element, HtmlFormElement};
+
+#[derive(Clone)]
+enum Check {
+    Required,
+    MinLen(usize),
+    MaxLen(usize),
+    Pattern { source: String, regex: RegExp },
+    Email,
+    Custom(Rc<dyn Fn(&str) -> bool>),
+}
+
+/// A check of the values of a field, with the message shown when it fails.
+///
+/// Every rule but `required` passes on an empty value, as in HTML, so an optional
+/// field can be left empty.
+#[derive(Clone)]
+pub struct Rule {
+    check: Check,
+    message: String,
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let check = match &self.check {
+            Check::Required => "required".to_owned(),
+            Check::MinLen(len) => format!("min_len({})", len),
+            Check::MaxLen(len) => format!("max_len({})", len),
+            Check::Pattern { source, .. } => format!("pattern({:?})", source),
+            Check::Email => "email".to_owned(),
+            Check::Custom(_) => "custom".to_owned(),
+        };
+        f.debug_struct("Rule")
+            .field("check", &check)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl Rule {
+    fn new(check: Check, message: &str) -> Self {
+        Rule {
+            check,
+            message: message.to_owned(),
+        }
+    }
+
+    /// At least one value not blank, a checked box for a checkbox.
+    pub fn required() -> Self {
+        Rule::new(Check::Required, "This field is required.")
+    }
+
+    /// At least len characters.
+    pub fn min_len(len: usize) -> Self {
+        Rule::new(Check::MinLen(len), &format!("Use at least {} characters.", len))
+    }
+
+    /// At most len characters.
+    pub fn max_len(len: usize) -> Self {
+        Rule::new(Check::MaxLen(len), &format!("Use at most {} characters.", len))
+    }
+
+    /// The whole value matching a JavaScript regular expression, like the `pattern`
+    /// attribute. The RegExp is compiled once, here, so it needs a JavaScript engine.
+    pub fn pattern(pattern: &str) -> Self {
+        Rule::try_pattern(pattern).or_panic()
+    }
+
+    /// A pattern rule, or a Parse error if pattern is not a valid RegExp.
+    pub fn try_pattern(pattern: &str) -> Result<Self, MinimalError> {
+        let regex = regex(pattern).ok_or_else(|| MinimalError::Parse {
+            target: Target::Member("pattern"),
+            expected: "RegExp",
+            found: pattern.to_owned(),
+        })?;
+        let check = Check::Pattern {
+            source: pattern.to_owned(),
+            regex,
+        };
+        Ok(Rule::new(check, "Match the requested format."))
+    }
+
+    /// An email address, as `<input type="email">` accepts it.
+    pub fn email() -> Self {
+        Rule::new(Check::Email, "Enter an email address.")
+    }
+
+    /// A value for which check returns true.
+    pub fn custom<F: Fn(&str) -> bool + 'static>(check: F) -> Self {
+        Rule::new(Check::Custom(Rc::new(check)), "Enter a valid value.")
+    }
+
+    /// Set the message shown when the rule fails.
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = message.to_owned();
+        self
+    }
+
+    /// Check the values of a field.
+    pub fn passes(&self, values: &[&str]) -> bool {
+        match &self.check {
+            Check::Required => values.iter().any(|value| !value.trim().is_empty()),
+            check => values
+                .iter()
+                .filter(|value| !value.is_empty())
+                .all(|value| passes(check, value)),
+        }
+    }
+}
+
+fn passes(check: &Check, value: &str) -> bool {
+    match check {
+        Check::Required => !value.trim().is_empty(),
+        Check::MinLen(len) => value.chars().count() >= *len,
+        Check::MaxLen(len) => value.chars().count() <= *len,
+        Check::Pattern { regex, .. } => regex.test(value),
+        Check::Email => is_email(value),
+        Check::Custom(check) => check(value),
+    }
+}
+
+/// Compile pattern as the `pattern` attribute does, `None` if it is invalid.
+fn regex(pattern: &str) -> Option<RegExp> {
+    let constructor: Function = Reflect::get(&js_sys::global(), &JsValue::from_str("RegExp"))
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let args = Array::of2(&JsValue::from_str(&format!("^(?:{})$", pattern)), &JsValue::from_str("u"));
+    Reflect::construct(&constructor, &args).ok()?.dyn_into().ok()
+}
+
+/// A valid email address of the HTML standard, like `name@example.com`.
+fn is_email(value: &str) -> bool {
+    let (local, domain) = match value.split_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let local_ok = !local.is_empty()
+        && local
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b".!#$%&'*+/=?^_`{|}~-".contains(&byte));
+    let domain_ok = domain.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    });
+    local_ok && domain_ok
+}
+
+/// A field failing its rules, with the message of the first rule failing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// The result of `check()` or `validate()`, one error per invalid field, in the
+/// order of the rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationReport {
+    /// Check if every field passed.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the message of field, `None` if it is valid.
+    pub fn message(&self, field: &str) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|error| error.field == field)
+            .map(|error| error.message.as_str())
+    }
+
+    /// Get the names of the invalid fields.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.errors.iter().map(|error| error.field.as_str())
+    }
+}
+
+/// Rules attached to the names of the fields of a form.
+///
+/// `check()` works on values alone, without a document, but a `pattern` rule runs a
+/// JavaScript RegExp and so needs the browser. `validate()` reads the fields under an
+/// element, and for each field sets `setCustomValidity()`, `aria-invalid`, and an
+/// error element after it, `<span id="{id or name}-error" class="field-error">`,
+/// linked with `aria-describedby`. When that id is taken by another element, or by
+/// the error element of another field, a number is added, like `email-error-2`.
+/// `report()` shows the message of the browser too.
+///
+/// # Examples
+///
+/// ```
+/// use Minimal::{QueryParams, Rule, Validator};
+///
+/// let validator = Validator::new()
+///     .rule("email", Rule::required())
+///     .rule("email", Rule::email())
+///     .rule("name", Rule::min_len(2).message("Your name is too short."))
+///     .rule("age", Rule::custom(|age| age.parse::<u8>().is_ok()));
+///
+/// let values: QueryParams = "email=ada%40example.com&name=A&age=".parse().unwrap();
+/// let report = validator.check(&values);
+/// assert!(!report.is_valid());
+/// assert_eq!(report.message("name"), Some("Your name is too short."));
+/// assert_eq!(report.message("email"), None);
+/// assert_eq!(report.fields().collect::<Vec<_>>(), vec!["name"]);
+///
+/// let values: QueryParams = "email=ada&name=Ada&age=300".parse().unwrap();
+/// let report = validator.check(&values);
+/// assert_eq!(report.message("email"), Some("Enter an email address."));
+/// assert_eq!(report.message("age"), Some("Enter a valid value."));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Validator {
+    fields: Vec<(String, Vec<Rule>)>,
+}
+
+impl Validator {
+    /// A Validator without rules.
+    pub fn new() -> Self {
+        Validator { fields: Vec::new() }
+    }
+
+    /// Add a rule to field, checked after the ones it already has.
+    pub fn rule(mut self, field: &str, rule: Rule) -> Self {
+        match self.fields.iter_mut().find(|(name, _)| name == field) {
+            Some((_, rules)) => rules.push(rule),
+            None => self.fields.push((field.to_owned(), vec![rule])),
+        }
+        self
+    }
+
+    /// Check values against the rules, a missing field having no value.
+    pub fn check(&self, values: &QueryParams) -> ValidationReport {
+        let errors = self
+            .fields
+            .iter()
+            .filter_map(|(field, rules)| {
+                let field_values = values.get_all(field);
+                rules
+                    .iter()
+                    .find(|rule| !rule.passes(&field_values))
+                    .map(|rule| FieldError {
+                        field: field.clone(),
+                        message: rule.message.clone(),
+                    })
+            })
+            .collect();
+        ValidationReport { errors }
+    }
+
+    /// Check the fields under root, and show the errors on them. Disabled fields are
+    /// not checked, as the browser does.
+    pub fn validate(&self, root: &Element) -> ValidationReport {
+        self.try_validate(root).or_panic()
+    }
+
+    /// Check the fields under root and show the errors, or the JavaScript exception.
+    pub fn try_validate(&self, root: &Element) -> Result<ValidationReport, MinimalError> {
+        self.mark(root).map(|(report, _)| report)
+    }
+
+    /// Check the fields under root, show the errors, and show the message of the
+    /// browser on the first invalid field, same as reportValidity().
+    pub fn report(&self, root: &Element) -> ValidationReport {
+        self.try_report(root).or_panic()
+    }
+
+    /// Check, show the errors and the message of the browser, or the JavaScript exception.
+    pub fn try_report(&self, root: &Element) -> Result<ValidationReport, MinimalError> {
+        let (report, first_invalid) = self.mark(root)?;
+        match root.dyn_ref::<HtmlFormElement>() {
+            Some(form) => {
+                form.report_validity();
+            }
+            None => {
+                if let Some(control) = first_invalid {
+                    control.report_validity();
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Check the fields under root and show the errors, getting the first invalid one.
+    fn mark(&self, root: &Element) -> Result<(ValidationReport, Option<Control>), MinimalError> {
+        let controls = controls(root)?;
+        let mut report = self.check(&root.try_values()?);
+        // The values of disabled fields are not sent, so their rules are skipped.
+        report.errors.retain(|error| {
+            controls
+                .iter()
+                .any(|(name, control)| *name == error.field && !is_disabled(control.element()))
+        });
+        for (field, _) in &self.fields {
+            let message = report.message(field).unwrap_or_default();
+            let fields: Vec<&Control> = controls
+                .iter()
+                .filter(|(name, _)| name == field)
+                .map(|(_, control)| control)
+                .collect();
+            let last = match fields.last() {
+                Some(last) => last.element(),
+                None => continue,
+            };
+            let document = last
+                .owner_document()
+                .ok_or(MinimalError::NotFound(Target::Member("owner_document")))?;
+            let id = last.id();
+            let (error_id, error) = error_element(&document, if id.is_empty() { field } else { &id }, &fields, last);
+            show_error(&document, last, error, &error_id, message, &Target::Field(field.clone()))?;
+            for control in fields {
+                control.set_custom_validity(message);
+                let element = control.element();
+                let target = || Target::Field(field.clone());
+                if message.is_empty() {
+                    element
+                        .remove_attribute("aria-invalid")
+                        .map_err(|e| MinimalError::js(target(), e))?;
+                } else {
+                    element
+                        .set_attribute("aria-invalid", "true")
+                        .map_err(|e| MinimalError::js(target(), e))?;
+                }
+                let described = element.get_attribute("aria-describedby").unwrap_or_default();
+                let described = toggle_token(&described, &error_id, !message.is_empty());
+                let result = if described.is_empty() {
+                    element.remove_attribute("aria-describedby")
+                } else {
+                    element.set_attribute("aria-describedby", &described)
+                };
+                result.map_err(|e| MinimalError::js(target(), e))?;
+            }
+        }
+        let first_invalid = controls
+            .into_iter()
+            .find(|(name, control)| report.message(name).is_some() && !is_disabled(control.element()))
+            .map(|(_, control)| control);
+        Ok((report, first_invalid))
+    }
+}
+
+/// Find the id of the error element of a field named base, with the element if it
+/// already exists. An element of class `field-error` is only taken as the field's own
+/// when a control of the field is described by it or when it is right after last, so
+/// a form with two fields of the same name does not share an error element, and any
+/// other element holding the id is skipped.
+fn error_element(
+    document: &Document,
+    base: &str,
+    fields: &[&Control],
+    last: &Element,
+) -> (String, Option<Element>) {
+    let next = last.next_element_sibling();
+    let linked = |id: &str| {
+        fields.iter().any(|control| {
+            let described = control.element().get_attribute("aria-describedby").unwrap_or_default();
+            described.split_whitespace().any(|token| token == id)
+        })
+    };
+    let mut id = format!("{}-error", base);
+    let mut number = 1;
+    loop {
+        match document.get_element_by_id(&id) {
+            Some(element)
+                if element.class_list().contains("field-error")
+                    && (linked(&id) || next.as_ref() == Some(&element)) =>
+            {
+                return (id, Some(element))
+            }
+            Some(_) => {
+                number += 1;
+                id = format!("{}-error-{}", base, number);
+            }
+            None => return (id, None),
+        }
+    }
+}
+
+/// Set the error element of a field after last, creating it the first time.
+fn show_error(
+    document: &Document,
+    last: &Element,
+    error: Option<Element>,
+    id: &str,
+    message: &str,
+    target: &Target,
+) -> Result<(), MinimalError> {
+    let error = match error {
+        Some(error) => error,
+        None if message.is_empty() => return Ok(()),
+        None => {
+            let error = document
+                .create_element("span")
+                .map_err(|e| MinimalError::js(Target::Member("create_element"), e))?;
+            error.set_id(id);
+            error.set_class_name("field-error");
+            error
+                .set_attribute("aria-live", "polite")
+                .map_err(|e| MinimalError::js(target.clone(), e))?;
+            last.after_with_node_1(&error)
+                .map_err(|e| MinimalError::js(target.clone(), e))?;
+            error
+        }
+    };
+    error.set_text_content(Some(message));
+    let hidden = if message.is_empty() {
+        error.set_attribute("hidden", "")
+    } else {
+        error.remove_attribute("hidden")
+    };
+    hidden.map_err(|e| MinimalError::js(target.clone(), e))
+}
+
+/// Add or remove token in a space-separated list, like `aria-describedby`.
+fn toggle_token(list: &str, token: &str, present: bool) -> String {
+    let mut tokens: Vec<&str> = list.split_whitespace().filter(|t| *t != token).collect();
+    if present {
+        tokens.push(token);
+    }
+    tokens.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_email, toggle_token, Rule, Validator};
+    use crate::url::QueryParams;
+
+    #[test]
+    fn emails() {
+        for value in ["a@b", "ada.lovelace+tag@example.co.uk", "a@b-c.d", "1@2.3"] {
+            assert!(is_email(value), "{}", value);
+        }
+        let long = "a".repeat(64);
+        for value in [
+            "",
+            "a",
+            "a@",
+            "@b",
+            "a@-b.com",
+            "a@b-.com",
+            "a@b..com",
+            "a@b.com.",
+            "a b@c.d",
+            "a@b@c",
+            "é@b.c",
+            long.as_str(),
+        ] {
+            assert!(!is_email(value), "{}", value);
+        }
+        assert!(is_email(&format!("a@{}.com", "b".repeat(63))));
+        assert!(!is_email(&format!("a@{}.com", long)));
+    }
+
+    #[test]
+    fn empty_values_pass_all_but_required() {
+        let rules = [Rule::min_len(3), Rule::max_len(1), Rule::email(), Rule::custom(|_| false)];
+        for rule in &rules {
+            assert!(rule.passes(&[]), "{:?}", rule);
+            assert!(rule.passes(&[""]), "{:?}", rule);
+        }
+        assert!(!Rule::required().passes(&[]));
+        assert!(!Rule::required().passes(&["", "  "]));
+        assert!(Rule::required().passes(&["", "a"]));
+    }
+
+    #[test]
+    fn lengths_count_chars() {
+        assert!(Rule::max_len(3).passes(&["été"]));
+        assert!(!Rule::min_len(4).passes(&["été"]));
+        assert!(Rule::min_len(2).passes(&["ab", ""]));
+        assert!(!Rule::min_len(2).passes(&["ab", "c"]));
+        assert!(Rule::custom(|value| value == "yes").passes(&["yes"]));
+    }
+
+    #[test]
+    fn first_failing_rule_wins() {
+        let validator = Validator::new()
+            .rule("name", Rule::required().message("required"))
+            .rule("name", Rule::min_len(3).message("short"))
+            .rule("name", Rule::max_len(1).message("long"))
+            .rule("other", Rule::required());
+        let report = validator.check(&QueryParams::from_query("name=ab&other=x"));
+        assert_eq!(report.message("name"), Some("short"));
+        assert_eq!(report.fields().collect::<Vec<_>>(), vec!["name"]);
+        let report = validator.check(&QueryParams::new());
+        assert_eq!(report.message("name"), Some("required"));
+        assert_eq!(report.fields().collect::<Vec<_>>(), vec!["name", "other"]);
+    }
+
+    #[test]
+    fn toggled_tokens() {
+        assert_eq!(toggle_token("hint", "name-error", true), "hint name-error");
+        assert_eq!(toggle_token("hint  name-error other", "name-error", false), "hint other");
+        assert_eq!(toggle_token("name-error hint", "name-error", true), "hint name-error");
+        assert_eq!(toggle_token("", "name-error", false), "");
+        assert_eq!(toggle_token("name-error-2", "name-error", false), "name-error-2");
+    }
+}
@@ -0,0 +1,934 @@
+//! Names and values of forms and queries mapped to Rust types with serde, in pure
+//! Rust.
+
+use crate::error::{short_type_name, MinimalError, Target};
+use crate::url::QueryParams;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug)]
+enum Error {
+    /// A value that does not parse as the type of its field.
+    Value {
+        field: String,
+        expected: &'static str,
+        found: String,
+    },
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Value { field, expected, found } => {
+                write!(f, "cannot parse {:?} as {} for {:?}", found, expected, field)
+            }
+            Error::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error::Custom(message.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error::Custom(message.to_string())
+    }
+}
+
+impl QueryParams {
+    /// Deserialize the values into T, like a struct with a field per name.
+    ///
+    /// A `Vec` field gets every value of its name, like checkboxes or a multi-select,
+    /// any other field gets the last one, so a hidden input before a checkbox gives
+    /// its default. An `Option` field is `None` when its name is missing or empty. A
+    /// `bool` is true for `on`, `true`, `yes` and `1`; an unchecked checkbox sends
+    /// nothing, and neither does an empty `Vec`, so give these fields
+    /// `#[serde(default)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use Minimal::QueryParams;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Signup {
+    ///     name: String,
+    ///     age: Option<u32>,
+    ///     tags: Vec<String>,
+    ///     #[serde(default)]
+    ///     newsletter: bool,
+    /// }
+    ///
+    /// let values: QueryParams = "name=Ada&age=&tags=rust&tags=wasm&newsletter=on".parse().unwrap();
+    /// let signup: Signup = values.to_struct().unwrap();
+    /// assert_eq!(signup, Signup {
+    ///     name: "Ada".to_owned(),
+    ///     age: None,
+    ///     tags: vec!["rust".to_owned(), "wasm".to_owned()],
+    ///     newsletter: true,
+    /// });
+    ///
+    /// let values: QueryParams = "name=Ada&age=old".parse().unwrap();
+    /// assert!(values.to_struct::<Signup>().is_err());
+    /// ```
+    pub fn to_struct<T: DeserializeOwned>(&self) -> Result<T, MinimalError> {
+        T::deserialize(Fields { params: self }).map_err(|error| match error {
+            Error::Value { field, expected, found } => MinimalError::Parse {
+                target: Target::Field(field),
+                expected,
+                found,
+            },
+            Error::Custom(message) => MinimalError::Parse {
+                target: Target::Member("to_struct"),
+                expected: short_type_name::<T>(),
+                found: message,
+            },
+        })
+    }
+
+    /// Serialize a struct or a map into values, in the order of its fields.
+    ///
+    /// A sequence gives a value per item, `None` gives no value, and a `bool` gives
+    /// `true` or `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use Minimal::QueryParams;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Filters {
+    ///     q: String,
+    ///     page: Option<u32>,
+    ///     tags: Vec<&'static str>,
+    /// }
+    ///
+    /// let filters = Filters { q: "red shoes".to_owned(), page: None, tags: vec!["a", "b"] };
+    /// let values = QueryParams::from_struct(&filters).unwrap();
+    /// assert_eq!(values.to_string(), "q=red+shoes&tags=a&tags=b");
+    /// ```
+    pub fn from_struct<T: Serialize + ?Sized>(value: &T) -> Result<QueryParams, MinimalError> {
+        let mut params = QueryParams::new();
+        value
+            .serialize(Pairs { params: &mut params })
+            .map_err(|error| MinimalError::Parse {
+                target: Target::Member("from_struct"),
+                expected: "names and values",
+                found: error.to_string(),
+            })?;
+        Ok(params)
+    }
+}
+
+/// The whole multimap, as a map of names to values.
+struct Fields<'a> {
+    params: &'a QueryParams,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Fields<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in self.params.iter() {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        visitor.visit_map(FieldMap {
+            params: self.params,
+            names: names.into_iter(),
+            current: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+struct FieldMap<'a, I> {
+    params: &'a QueryParams,
+    names: I,
+    current: Option<&'a str>,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a str>> MapAccess<'de> for FieldMap<'a, I> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        match self.names.next() {
+            Some(name) => {
+                self.current = Some(name);
+                seed.deserialize(name.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Error> {
+        let name = self.current.take().unwrap_or_default();
+        seed.deserialize(Field {
+            name,
+            values: self.params.get_all(name),
+        })
+    }
+}
+
+/// Every value of a name, for one field.
+struct Field<'a> {
+    name: &'a str,
+    values: Vec<&'a str>,
+}
+
+impl<'a> Field<'a> {
+    fn last(&self) -> Value<'a> {
+        Value {
+            field: self.name,
+            value: self.values.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+macro_rules! forward_to_last {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.last().$method(visitor)
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Field<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.values.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else {
+            self.last().deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.values.iter().all(|value| value.is_empty()) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Values {
+            field: self.name,
+            values: self.values.into_iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.last().deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.last().deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_last! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_unit deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+}
+
+struct Values<'a, I> {
+    field: &'a str,
+    values: I,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a str>> SeqAccess<'de> for Values<'a, I> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        match self.values.next() {
+            Some(value) => seed.deserialize(Value { field: self.field, value }).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// One value, parsed as the type asked for.
+struct Value<'a> {
+    field: &'a str,
+    value: &'a str,
+}
+
+impl<'a> Value<'a> {
+    fn parse<T: FromStr>(&self, expected: &'static str) -> Result<T, Error> {
+        self.value.trim().parse().map_err(|_| self.error(expected))
+    }
+
+    fn error(&self, expected: &'static str) -> Error {
+        Error::Value {
+            field: self.field.to_owned(),
+            expected,
+            found: self.value.to_owned(),
+        }
+    }
+}
+
+macro_rules! parse_value {
+    ($($method:ident $visit:ident $ty:ty,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.$visit(self.parse::<$ty>(stringify!($ty))?)
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Value<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str(self.value)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value.trim().to_ascii_lowercase().as_str() {
+            "on" | "true" | "yes" | "1" => visitor.visit_bool(true),
+            "" | "off" | "false" | "no" | "0" => visitor.visit_bool(false),
+            _ => Err(self.error("bool")),
+        }
+    }
+
+    parse_value! {
+        deserialize_i8 visit_i8 i8,
+        deserialize_i16 visit_i16 i16,
+        deserialize_i32 visit_i32 i32,
+        deserialize_i64 visit_i64 i64,
+        deserialize_i128 visit_i128 i128,
+        deserialize_u8 visit_u8 u8,
+        deserialize_u16 visit_u16 u16,
+        deserialize_u32 visit_u32 u32,
+        deserialize_u64 visit_u64 u64,
+        deserialize_u128 visit_u128 u128,
+        deserialize_f32 visit_f32 f32,
+        deserialize_f64 visit_f64 f64,
+        deserialize_char visit_char char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.value.into_deserializer())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// The top of `from_struct()`, writing a struct or a map into params.
+struct Pairs<'a> {
+    params: &'a mut QueryParams,
+}
+
+fn not_pairs() -> Error {
+    Error::Custom("expected a struct or a map of names to values".to_owned())
+}
+
+macro_rules! reject {
+    ($($method:ident $ty:ty,)*) => {$(
+        fn $method(self, _value: $ty) -> Result<Self::Ok, Error> {
+            Err(not_pairs())
+        }
+    )*};
+}
+
+impl<'a> ser::Serializer for Pairs<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = PairsMap<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    reject! {
+        serialize_bool bool,
+        serialize_i8 i8,
+        serialize_i16 i16,
+        serialize_i32 i32,
+        serialize_i64 i64,
+        serialize_u8 u8,
+        serialize_u16 u16,
+        serialize_u32 u32,
+        serialize_u64 u64,
+        serialize_f32 f32,
+        serialize_f64 f64,
+        serialize_char char,
+        serialize_str &str,
+        serialize_bytes &[u8],
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, _variant: &'static str) -> Result<(), Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(not_pairs())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(PairsMap {
+            params: self.params,
+            name: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(not_pairs())
+    }
+}
+
+impl<'a> SerializeStruct for Pairs<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, name: &'static str, value: &T) -> Result<(), Error> {
+        for value in value.serialize(ValueWriter { nested: false })? {
+            self.params.append(name, value);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct PairsMap<'a> {
+    params: &'a mut QueryParams,
+    name: Option<String>,
+}
+
+impl<'a> SerializeMap for PairsMap<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        let mut name = key.serialize(ValueWriter { nested: true })?;
+        if name.len() != 1 {
+            return Err(Error::Custom("expected a name as key".to_owned()));
+        }
+        self.name = name.pop();
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let name = self.name.take().unwrap_or_default();
+        for value in value.serialize(ValueWriter { nested: false })? {
+            self.params.append(&name, value);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// The values of one field: one for a scalar, none for `None`, one per item for a
+/// sequence, unless nested in another sequence.
+struct ValueWriter {
+    nested: bool,
+}
+
+fn not_value() -> Error {
+    Error::Custom("expected a value or a sequence of values".to_owned())
+}
+
+macro_rules! write_value {
+    ($($method:ident $ty:ty,)*) => {$(
+        fn $method(self, value: $ty) -> Result<Vec<String>, Error> {
+            Ok(vec![value.to_string()])
+        }
+    )*};
+}
+
+impl ser::Serializer for ValueWriter {
+    type Ok = Vec<String>;
+    type Error = Error;
+    type SerializeSeq = SeqWriter;
+    type SerializeTuple = SeqWriter;
+    type SerializeTupleStruct = SeqWriter;
+    type SerializeTupleVariant = Impossible<Vec<String>, Error>;
+    type SerializeMap = Impossible<Vec<String>, Error>;
+    type SerializeStruct = Impossible<Vec<String>, Error>;
+    type SerializeStructVariant = Impossible<Vec<String>, Error>;
+
+    write_value! {
+        serialize_bool bool,
+        serialize_i8 i8,
+        serialize_i16 i16,
+        serialize_i32 i32,
+        serialize_i64 i64,
+        serialize_i128 i128,
+        serialize_u8 u8,
+        serialize_u16 u16,
+        serialize_u32 u32,
+        serialize_u64 u64,
+        serialize_u128 u128,
+        serialize_f32 f32,
+        serialize_f64 f64,
+        serialize_char char,
+        serialize_str &str,
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Vec<String>, Error> {
+        Ok(vec![String::from_utf8_lossy(value).into_owned()])
+    }
+
+    fn serialize_none(self) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<String>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![variant.to_owned()])
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Vec<String>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Vec<String>, Error> {
+        Err(not_value())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqWriter, Error> {
+        if self.nested {
+            return Err(not_value());
+        }
+        Ok(SeqWriter { values: Vec::new() })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqWriter, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqWriter, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(not_value())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(not_value())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(not_value())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(not_value())
+    }
+}
+
+struct SeqWriter {
+    values: Vec<String>,
+}
+
+impl SeqWriter {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.values.extend(value.serialize(ValueWriter { nested: true })?);
+        Ok(())
+    }
+}
+
+impl SerializeSeq for SeqWriter {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.values)
+    }
+}
+
+impl SerializeTuple for SeqWriter {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.values)
+    }
+}
+
+impl SerializeTupleStruct for SeqWriter {
+    type Ok = Vec<String>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{MinimalError, Target};
+    use crate::url::QueryParams;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    fn params(query: &str) -> QueryParams {
+        query.parse().unwrap()
+    }
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Plan {
+        Free,
+        Pro,
+    }
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Account {
+        name: String,
+        age: Option<u32>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        admin: bool,
+        plan: Plan,
+        score: f64,
+    }
+
+    #[test]
+    fn last_value_wins() {
+        #[derive(Deserialize)]
+        struct Form {
+            agree: String,
+            count: u8,
+        }
+        let form: Form = params("agree=off&count=1&agree=on&count=2").to_struct().unwrap();
+        assert_eq!(form.agree, "on");
+        assert_eq!(form.count, 2);
+    }
+
+    #[test]
+    fn vec_collects_every_value() {
+        #[derive(Deserialize)]
+        struct Form {
+            tags: Vec<String>,
+            ids: Vec<u32>,
+            #[serde(default)]
+            none: Vec<String>,
+        }
+        let form: Form = params("tags=a&ids=3&tags=b&ids=1&tags=").to_struct().unwrap();
+        assert_eq!(form.tags, vec!["a", "b", ""]);
+        assert_eq!(form.ids, vec![3, 1]);
+        assert!(form.none.is_empty());
+    }
+
+    #[test]
+    fn option_is_none_when_missing_or_empty() {
+        #[derive(Deserialize)]
+        struct Form {
+            missing: Option<u32>,
+            empty: Option<u32>,
+            given: Option<u32>,
+            text: Option<String>,
+        }
+        let form: Form = params("empty=&given=4&text=hi").to_struct().unwrap();
+        assert_eq!(form.missing, None);
+        assert_eq!(form.empty, None);
+        assert_eq!(form.given, Some(4));
+        assert_eq!(form.text.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn bool_spellings() {
+        #[derive(Deserialize)]
+        struct Form {
+            flag: bool,
+        }
+        let flag = |value: &str| params(&format!("flag={}", value)).to_struct::<Form>().map(|form| form.flag);
+        for value in ["on", "true", "yes", "1", "ON", "True", "%20yes%20"] {
+            assert_eq!(flag(value), Ok(true), "{}", value);
+        }
+        for value in ["", "off", "false", "no", "0", "NO"] {
+            assert_eq!(flag(value), Ok(false), "{}", value);
+        }
+        assert_eq!(
+            flag("maybe"),
+            Err(MinimalError::Parse {
+                target: Target::Field("flag".to_owned()),
+                expected: "bool",
+                found: "maybe".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn number_errors_name_the_field() {
+        #[derive(Deserialize, Debug)]
+        struct Form {
+            #[allow(dead_code)]
+            age: u8,
+        }
+        assert_eq!(
+            params("age=300").to_struct::<Form>().unwrap_err(),
+            MinimalError::Parse {
+                target: Target::Field("age".to_owned()),
+                expected: "u8",
+                found: "300".to_owned(),
+            }
+        );
+        assert_eq!(
+            params("age=abc").to_struct::<Form>().unwrap_err().target(),
+            &Target::Field("age".to_owned())
+        );
+    }
+
+    #[test]
+    fn missing_field_is_an_error() {
+        #[derive(Deserialize, Debug)]
+        struct Form {
+            #[allow(dead_code)]
+            tags: Vec<String>,
+        }
+        assert!(params("").to_struct::<Form>().is_err());
+        let error = params("name=Ada").to_struct::<Account>().unwrap_err();
+        assert_eq!(error.target(), &Target::Member("to_struct"));
+    }
+
+    #[test]
+    fn maps_and_enums() {
+        let map: HashMap<String, String> = params("a=1&b=2").to_struct().unwrap();
+        assert_eq!(map.get("b").map(String::as_str), Some("2"));
+        assert!(params("plan=gold&name=a&score=1").to_struct::<Account>().is_err());
+    }
+
+    #[test]
+    fn from_struct_writes_fields_in_order() {
+        let account = Account {
+            name: "Ada Lovelace".to_owned(),
+            age: None,
+            tags: vec!["math".to_owned(), "poetry".to_owned()],
+            admin: false,
+            plan: Plan::Pro,
+            score: 9.5,
+        };
+        let values = QueryParams::from_struct(&account).unwrap();
+        assert_eq!(
+            values.to_string(),
+            "name=Ada+Lovelace&tags=math&tags=poetry&admin=false&plan=pro&score=9.5"
+        );
+    }
+
+    #[test]
+    fn from_struct_to_struct_round_trip() {
+        let account = Account {
+            name: "Grace & co".to_owned(),
+            age: Some(85),
+            tags: vec![],
+            admin: true,
+            plan: Plan::Free,
+            score: -0.25,
+        };
+        let values = QueryParams::from_struct(&account).unwrap();
+        let text = values.to_string();
+        assert_eq!(params(&text).to_struct::<Account>(), Ok(account));
+    }
+
+    #[test]
+    fn from_struct_needs_names() {
+        let error = QueryParams::from_struct(&vec![1, 2]).unwrap_err();
+        assert_eq!(error.target(), &Target::Member("from_struct"));
+        let nested = [("a", vec![vec![1]])].into_iter().collect::<HashMap<_, _>>();
+        assert!(QueryParams::from_struct(&nested).is_err());
+    }
+}